@@ -0,0 +1,1068 @@
+let currentEntries = [];
+
+// Server-side settings (see /api/settings); replaced once they are loaded.
+let settings = {
+    read_only: false,
+    page_size: 100,
+    display: { title: 'Key-Value Store Contents', preview_length: 100 },
+};
+
+// Bearer token for actors configured with `auth.token`, kept per browser.
+const TOKEN_KEY = 'store-visualizer-token';
+
+// fetch() that sends the API token and asks for one when the server rejects
+// the request with 401.
+async function apiFetch(url, options = {}) {
+    const send = () => {
+        const headers = new Headers(options.headers || {});
+        // The actor has no clock; timestamps (e.g. snapshot names) use ours.
+        headers.set('X-Client-Time', Date.now());
+        const token = localStorage.getItem(TOKEN_KEY);
+        if (token) headers.set('Authorization', `Bearer ${token}`);
+        return fetch(url, { ...options, headers });
+    };
+    let response = await send();
+    if (response.status === 401) {
+        const token = prompt('This visualizer requires an access token:');
+        if (token) {
+            localStorage.setItem(TOKEN_KEY, token);
+            response = await send();
+        }
+    }
+    return response;
+}
+
+// Name of the selected store; null until /api/stores has been loaded, in
+// which case the unprefixed API (the default store) is used.
+const STORE_KEY = 'store-visualizer-store';
+let currentStore = null;
+
+// URL of a store-scoped API endpoint, e.g. storeApi('/search').
+function storeApi(path) {
+    return currentStore === null
+        ? `/api${path}`
+        : `/api/stores/${encodeURIComponent(currentStore)}${path}`;
+}
+
+async function loadStores() {
+    const select = document.getElementById('store-select');
+    try {
+        const response = await apiFetch('/api/stores?probe=true');
+        const data = await response.json();
+        if (data.status !== 'success') {
+            console.error('Failed to load stores', data);
+            return;
+        }
+        select.innerHTML = '';
+        const names = document.getElementById('store-names');
+        names.innerHTML = '';
+        data.stores.forEach(store => {
+            const name = document.createElement('option');
+            name.value = store.name;
+            names.appendChild(name);
+            const option = document.createElement('option');
+            option.value = store.name;
+            option.textContent = store.reachable === false
+                ? `${store.name} (unreachable)`
+                : store.name;
+            option.title = store.error || store.store_id;
+            select.appendChild(option);
+        });
+        const saved = localStorage.getItem(STORE_KEY);
+        currentStore = data.stores.some(store => store.name === saved) ? saved : data.default;
+        select.value = currentStore;
+        select.hidden = data.stores.length < 2;
+    } catch (error) {
+        console.error('Error loading stores:', error);
+    }
+}
+
+async function switchStore(name) {
+    currentStore = name;
+    localStorage.setItem(STORE_KEY, name);
+    selectedEntry = null;
+    document.querySelector('.details-content').innerHTML = '<p>Select an entry to view details</p>';
+    document.querySelector('.action-buttons').innerHTML = '';
+    clearQuery();
+    await loadSettings();
+    reloadFromStart();
+    connectLive();
+}
+
+async function loadSettings() {
+    try {
+        const response = await apiFetch(storeApi('/settings'));
+        const data = await response.json();
+        if (data.status === 'success') {
+            settings = data;
+        }
+    } catch (error) {
+        console.error('Error loading settings:', error);
+    }
+    document.title = settings.display.title;
+    document.querySelector('header h1').textContent = settings.display.title;
+    document.getElementById('new-entry').disabled = settings.read_only;
+}
+
+// Paging state. The server hands out an opaque cursor for the page after the
+// current one; previous pages are reached through the stack of cursors we
+// have already visited.
+let sortBy = 'key';
+let sortOrder = 'asc';
+let currentCursor = null;
+let nextCursor = null;
+let previousCursors = [];
+let totalEntries = 0;
+
+// Collects the search controls into /api/search query parameters. Returns an
+// empty object when no filter is set, in which case the plain listing is used.
+function searchFilters() {
+    const filters = {};
+    const text = document.getElementById('search').value;
+    if (text) {
+        const mode = document.getElementById('search-mode').value;
+        filters[mode === 'contains' ? 'key_contains' : mode] = text;
+    }
+    const valueContains = document.getElementById('filter-contains').value;
+    if (valueContains) filters.contains = valueContains;
+    const minSize = document.getElementById('filter-min-size').value;
+    if (minSize) filters.min_size = minSize;
+    const maxSize = document.getElementById('filter-max-size').value;
+    if (maxSize) filters.max_size = maxSize;
+    const type = document.getElementById('filter-type').value;
+    if (type) filters.type = type;
+    return filters;
+}
+
+async function fetchStoreContents(cursor = null) {
+    try {
+        const filters = searchFilters();
+        const endpoint = storeApi(Object.keys(filters).length > 0 ? '/search' : '/store-contents');
+        const params = new URLSearchParams({ ...filters, limit: settings.page_size, sort: sortBy, order: sortOrder });
+        if (cursor) {
+            params.set('cursor', cursor);
+        }
+        const response = await apiFetch(`${endpoint}?${params}`);
+        const data = await response.json();
+        if (data.status === 'success' && Array.isArray(data.entries)) {
+            currentEntries = data.entries;
+            currentCursor = cursor;
+            nextCursor = data.next_cursor;
+            totalEntries = data.total;
+            renderEntries(currentEntries);
+            renderPager();
+        } else {
+            console.error('Failed to fetch store contents', data);
+            showListError(data.code === 'store_unreachable'
+                ? 'The store actor is unreachable.'
+                : data.message || 'Failed to load store contents');
+        }
+    } catch (error) {
+        console.error('Error fetching store contents:', error);
+        showListError('Failed to load store contents');
+    }
+}
+
+function showListError(message) {
+    const container = document.querySelector('.store-entries');
+    container.innerHTML = '';
+    const div = document.createElement('div');
+    div.className = 'list-error';
+    div.textContent = message;
+    container.appendChild(div);
+}
+
+function renderEntries(entries) {
+    const container = document.querySelector('.store-entries');
+    container.innerHTML = '';
+
+    entries.forEach(entry => {
+        const div = document.createElement('div');
+        div.className = 'entry';
+        
+        // The actor sends a preview of JSON and text values, cut to
+        // display.preview_length; other values are listed by type and size.
+        let valuePreview;
+        if (entry.preview !== null) {
+            valuePreview = entry.preview_truncated ? entry.preview + '...' : entry.preview;
+        } else {
+            valuePreview = `${entry.content_type} data, ${entry.size} bytes`;
+        }
+
+        div.innerHTML = `
+            <strong>${escapeHtml(entry.key)}</strong>
+            <span class="content-type-badge ${entry.category}">${entry.content_type}</span>
+            <div class="value-preview">${escapeHtml(valuePreview)}</div>
+        `;
+
+        div.addEventListener('click', () => showDetails(entry));
+        container.appendChild(div);
+    });
+}
+
+function renderPager() {
+    const pageNumber = previousCursors.length + 1;
+    const pageCount = Math.max(1, Math.ceil(totalEntries / settings.page_size));
+    document.getElementById('page-info').textContent =
+        `Page ${pageNumber} of ${pageCount} (${totalEntries} entries)`;
+    document.getElementById('prev-page').disabled = previousCursors.length === 0;
+    document.getElementById('next-page').disabled = !nextCursor;
+}
+
+function reloadFromStart() {
+    previousCursors = [];
+    fetchStoreContents();
+}
+
+function nextPage() {
+    if (!nextCursor) return;
+    previousCursors.push(currentCursor);
+    fetchStoreContents(nextCursor);
+}
+
+function previousPage() {
+    if (previousCursors.length === 0) return;
+    fetchStoreContents(previousCursors.pop());
+}
+
+let selectedEntry = null;
+
+// Loads a single entry from the store actor. JSON values come back parsed,
+// anything else base64-encoded.
+async function fetchEntry(key) {
+    try {
+        const response = await apiFetch(storeApi(`/entries/${encodeURIComponent(key)}`));
+        const data = await response.json();
+        if (data.status === 'success') {
+            return data;
+        }
+        console.error('Failed to fetch entry', data);
+    } catch (error) {
+        console.error('Error fetching entry:', error);
+    }
+    return null;
+}
+
+async function showDetails(entry) {
+    selectedEntry = entry;
+    // Update selected state
+    document.querySelectorAll('.entry').forEach(el => el.classList.remove('selected'));
+    // Find the entry by looking at all entries and matching the key
+    const entries = document.querySelectorAll('.entry');
+    entries.forEach(el => {
+        if (el.querySelector('strong').textContent === entry.key) {
+            el.classList.add('selected');
+        }
+    });
+
+    const detailsContent = document.querySelector('.details-content');
+    detailsContent.innerHTML = `
+        <h3>Key: ${escapeHtml(entry.key)}</h3>
+        <p>Loading...</p>
+    `;
+
+    // Binary values are paged through the hex dump rather than fetched whole.
+    // Entries opened without list metadata are probed with its first page.
+    let firstPage = null;
+    if (!entry.category || usesHexViewer(entry.category)) {
+        firstPage = await fetchHexdump(entry.key, 0, HEXDUMP_PAGE);
+        if (selectedEntry !== entry) return;
+    }
+    const binary = firstPage !== null && usesHexViewer(firstPage.category);
+    const detail = binary ? null : await fetchEntry(entry.key);
+    if (selectedEntry !== entry) return;
+    if (binary) {
+        detailsContent.innerHTML = `
+            <h3>Key: ${escapeHtml(entry.key)}</h3>
+            <p>${firstPage.content_type} data (${firstPage.mime}), ${firstPage.size} bytes:</p>
+            <div class="hex-viewer"></div>
+        `;
+    } else if (!detail) {
+        detailsContent.innerHTML = `
+            <h3>Key: ${escapeHtml(entry.key)}</h3>
+            <p>Failed to load entry</p>
+        `;
+    } else if (detail.format === 'json') {
+        detailsContent.innerHTML = `
+            <h3>Key: ${escapeHtml(entry.key)}</h3>
+            <pre>${escapeHtml(JSON.stringify(detail.value, null, 2))}</pre>
+        `;
+    } else {
+        let body;
+        if (detail.category === 'text') {
+            const bytes = Uint8Array.from(atob(detail.value), c => c.charCodeAt(0));
+            body = `<p>${detail.content_type.toUpperCase()} text:</p><pre>${escapeHtml(new TextDecoder().decode(bytes))}</pre>`;
+        } else if (detail.category === 'image') {
+            body = `<p>${detail.content_type.toUpperCase()} image, ${detail.size} bytes:</p>
+                <img class="image-preview" src="data:${detail.mime};base64,${detail.value}" alt="${escapeHtml(entry.key)}">`;
+        } else {
+            body = `<p>${detail.content_type} data (${detail.mime}), ${detail.size} bytes:</p>
+                <div class="hex-viewer"></div>`;
+        }
+        detailsContent.innerHTML = `
+            <h3>Key: ${escapeHtml(entry.key)}</h3>
+            ${body}
+        `;
+    }
+
+    // Update action buttons
+    const actionButtons = document.querySelector('.action-buttons');
+    actionButtons.innerHTML = `
+        <button onclick="copyToClipboard('${entry.key}')">Copy ID</button>
+    `;
+    const hexViewer = detailsContent.querySelector('.hex-viewer');
+    if (hexViewer) {
+        renderHexViewer(hexViewer, entry.key, binary ? firstPage : null);
+    }
+    if (detail && detail.format === 'json' && !detail.read_only) {
+        const editButton = document.createElement('button');
+        editButton.textContent = 'Edit';
+        editButton.addEventListener('click', () =>
+            openEditor(entry.key, JSON.stringify(detail.value, null, 2))
+        );
+        actionButtons.appendChild(editButton);
+    }
+}
+
+const HEXDUMP_PAGE = 512;
+
+// Categories shown in the hex viewer instead of as text or an image.
+function usesHexViewer(category) {
+    return !['json', 'text', 'image'].includes(category);
+}
+
+async function fetchHexdump(key, offset, length) {
+    const params = new URLSearchParams({ offset, length });
+    try {
+        const response = await apiFetch(storeApi(`/entries/${encodeURIComponent(key)}/hexdump?${params}`));
+        const data = await response.json();
+        if (data.status === 'success') {
+            return data;
+        }
+        console.error('Failed to fetch hex dump', data);
+    } catch (error) {
+        console.error('Error fetching hex dump:', error);
+    }
+    return null;
+}
+
+// The bytes of a hex dump page, read back from its rows.
+function hexdumpBytes(page) {
+    return Uint8Array.from(
+        page.rows.flatMap(row => row.hex.split(' ').filter(Boolean)),
+        hex => parseInt(hex, 16)
+    );
+}
+
+function bytesToHex(bytes) {
+    return Array.from(bytes, b => b.toString(16).padStart(2, '0')).join('');
+}
+
+function bytesToBase64(bytes) {
+    let binary = '';
+    bytes.forEach(b => { binary += String.fromCharCode(b); });
+    return btoa(binary);
+}
+
+// Pages through a binary value with /api/entries/:key/hexdump, starting from
+// `firstPage` if it was already fetched. The From / Length inputs select the
+// byte range shown and copied; only that range is ever requested.
+function renderHexViewer(container, key, firstPage) {
+    container.innerHTML = `
+        <div class="hex-range">
+            <label>From <input type="number" class="hex-from" min="0" value="0"></label>
+            <label>Length <input type="number" class="hex-length" min="1" value="${HEXDUMP_PAGE}"></label>
+            <button class="hex-prev">Previous</button>
+            <button class="hex-next">Next</button>
+            <span class="hex-info"></span>
+        </div>
+        <pre class="hexdump"></pre>
+    `;
+    const from = container.querySelector('.hex-from');
+    const length = container.querySelector('.hex-length');
+    const prev = container.querySelector('.hex-prev');
+    const next = container.querySelector('.hex-next');
+    const info = container.querySelector('.hex-info');
+    const dump = container.querySelector('.hexdump');
+    let page = null;
+
+    const show = data => {
+        page = data;
+        from.max = Math.max(0, data.size - 1);
+        const hexWidth = data.width * 3 - 1;
+        dump.textContent = data.rows
+            .map(row => `${row.offset.toString(16).padStart(8, '0')}  ${row.hex.padEnd(hexWidth)}  |${row.ascii}|`)
+            .join('\n');
+        info.textContent = `bytes ${data.offset}-${data.offset + data.length - 1} of ${data.size}`;
+        prev.disabled = data.prev_offset === null;
+        next.disabled = data.next_offset === null;
+        prev.onclick = () => { from.value = data.prev_offset; load(); };
+        next.onclick = () => { from.value = data.next_offset; load(); };
+    };
+    const load = async () => {
+        page = null;
+        const data = await fetchHexdump(key, from.value || 0, length.value || HEXDUMP_PAGE);
+        if (data) {
+            show(data);
+        } else {
+            dump.textContent = 'Error loading hex dump';
+        }
+    };
+    from.addEventListener('change', load);
+    length.addEventListener('change', load);
+    if (firstPage) {
+        show(firstPage);
+    } else {
+        load();
+    }
+
+    const copy = (encode, button) => {
+        if (page) copyToClipboard(encode(hexdumpBytes(page)), button);
+    };
+    const actionButtons = document.querySelector('.action-buttons');
+    const copyHex = document.createElement('button');
+    copyHex.textContent = 'Copy as hex';
+    copyHex.addEventListener('click', () => copy(bytesToHex, copyHex));
+    const copyBase64 = document.createElement('button');
+    copyBase64.textContent = 'Copy as base64';
+    copyBase64.addEventListener('click', () => copy(bytesToBase64, copyBase64));
+    actionButtons.append(copyHex, copyBase64);
+}
+
+// Shows a JSON editor in the details panel. `key` is null for a new entry.
+function openEditor(key, initialText) {
+    const detailsContent = document.querySelector('.details-content');
+    detailsContent.innerHTML = `
+        <h3>${key === null ? 'New entry' : `Editing: ${escapeHtml(key)}`}</h3>
+        <textarea class="editor" spellcheck="false"></textarea>
+        <div class="editor-status"></div>
+    `;
+    const editor = detailsContent.querySelector('.editor');
+    const status = detailsContent.querySelector('.editor-status');
+    editor.value = initialText;
+
+    const validate = () => {
+        try {
+            JSON.parse(editor.value);
+            status.textContent = 'Valid JSON';
+            status.className = 'editor-status valid';
+            return true;
+        } catch (e) {
+            status.textContent = `Invalid JSON: ${e.message}`;
+            status.className = 'editor-status invalid';
+            return false;
+        }
+    };
+    editor.addEventListener('input', validate);
+    validate();
+
+    const actionButtons = document.querySelector('.action-buttons');
+    actionButtons.innerHTML = '';
+    const saveButton = document.createElement('button');
+    saveButton.textContent = 'Save';
+    saveButton.addEventListener('click', async () => {
+        if (!validate()) return;
+        const url = storeApi(key === null ? '/entries' : `/entries/${encodeURIComponent(key)}`);
+        try {
+            const response = await apiFetch(url, {
+                method: key === null ? 'POST' : 'PUT',
+                headers: { 'Content-Type': 'application/json' },
+                body: editor.value,
+            });
+            const data = await response.json();
+            if (data.status !== 'success') {
+                status.textContent = `Save failed (${response.status}): ${data.message}`;
+                status.className = 'editor-status invalid';
+                return;
+            }
+            reloadFromStart();
+            if (data.key_changed) {
+                alert(`The store saved the value under ${data.key}; ${key} keeps its old value.`);
+            }
+            if (data.key) {
+                showDetails({ key: data.key });
+            } else {
+                status.textContent = 'Saved';
+                status.className = 'editor-status valid';
+            }
+        } catch (error) {
+            status.textContent = `Save failed: ${error}`;
+            status.className = 'editor-status invalid';
+        }
+    });
+    const cancelButton = document.createElement('button');
+    cancelButton.textContent = 'Cancel';
+    cancelButton.addEventListener('click', () => {
+        if (key === null) {
+            detailsContent.innerHTML = '<p>Select an entry to view details</p>';
+            actionButtons.innerHTML = '';
+        } else {
+            showDetails({ key });
+        }
+    });
+    actionButtons.append(saveButton, cancelButton);
+}
+
+let searchTimer = null;
+
+// Re-runs the search shortly after the user stops typing.
+function scheduleSearch() {
+    clearTimeout(searchTimer);
+    searchTimer = setTimeout(reloadFromStart, 300);
+}
+
+function escapeHtml(text) {
+    const div = document.createElement('div');
+    div.textContent = text;
+    return div.innerHTML;
+}
+
+async function runQuery() {
+    const expression = document.getElementById('query-input').value.trim();
+    if (!expression) {
+        clearQuery();
+        return;
+    }
+    const status = document.querySelector('.query-status');
+    const table = document.querySelector('.query-results');
+    const params = new URLSearchParams({ ...searchFilters(), q: expression, limit: settings.page_size });
+    try {
+        const response = await apiFetch(storeApi(`/query?${params}`));
+        const data = await response.json();
+        if (data.status !== 'success') {
+            status.textContent = data.message || 'Query failed';
+            table.hidden = true;
+            return;
+        }
+        status.textContent = `${data.total} matching entries (${data.scanned} JSON values scanned, ${data.skipped} non-JSON skipped)`;
+        const body = table.querySelector('tbody');
+        body.innerHTML = '';
+        data.results.forEach(result => {
+            const row = document.createElement('tr');
+            const values = result.values.map(v => JSON.stringify(v, null, 2)).join('\n');
+            row.innerHTML = `<td>${escapeHtml(result.key)}</td><td><pre>${escapeHtml(values)}</pre></td>`;
+            row.addEventListener('click', () => showDetails({ key: result.key }));
+            body.appendChild(row);
+        });
+        table.hidden = data.results.length === 0;
+    } catch (error) {
+        console.error('Error running query:', error);
+        status.textContent = 'Error running query';
+    }
+}
+
+// Formats the structured changes of one key as "op /path: left -> right" lines.
+function describeChanges(changes) {
+    if (!changes) return '';
+    if (changes.length === 0) return 'Same JSON, different bytes';
+    return changes.map(change => {
+        const left = change.left === undefined ? '' : JSON.stringify(change.left);
+        const right = change.right === undefined ? '' : JSON.stringify(change.right);
+        const path = change.path || '/';
+        if (change.op === 'add') return `+ ${path}: ${right}`;
+        if (change.op === 'remove') return `- ${path}: ${left}`;
+        return `~ ${path}: ${left} -> ${right}`;
+    }).join('\n');
+}
+
+async function runDiff() {
+    const left = document.getElementById('diff-left').value.trim();
+    const right = document.getElementById('diff-right').value.trim();
+    const status = document.querySelector('.diff-status');
+    const table = document.querySelector('.diff-results');
+    if (!left || !right) {
+        status.textContent = 'Choose both sides to compare';
+        return;
+    }
+    const params = new URLSearchParams({
+        left,
+        right,
+        include_identical: document.getElementById('diff-identical').checked,
+    });
+    status.textContent = 'Comparing...';
+    try {
+        const response = await apiFetch(`/api/diff?${params}`);
+        const data = await response.json();
+        if (data.status !== 'success') {
+            status.textContent = data.message || 'Comparison failed';
+            table.hidden = true;
+            return;
+        }
+        const s = data.summary;
+        status.textContent = `${s.added} added, ${s.removed} removed, ${s.changed} changed, ${s.identical} identical`;
+        const body = table.querySelector('tbody');
+        body.innerHTML = '';
+        data.keys.forEach(diff => {
+            const row = document.createElement('tr');
+            const sizes = `${diff.left_size ?? '-'} / ${diff.right_size ?? '-'}`;
+            row.innerHTML = `
+                <td>${escapeHtml(diff.key)}</td>
+                <td class="diff-${diff.status}">${diff.status}</td>
+                <td>${sizes}</td>
+                <td><pre>${escapeHtml(describeChanges(diff.changes))}</pre></td>
+            `;
+            body.appendChild(row);
+        });
+        table.hidden = data.keys.length === 0;
+    } catch (error) {
+        console.error('Error comparing stores:', error);
+        status.textContent = 'Error comparing stores';
+    }
+}
+
+function formatSize(bytes) {
+    if (bytes < 1024) return `${bytes} B`;
+    if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KiB`;
+    return `${(bytes / (1024 * 1024)).toFixed(1)} MiB`;
+}
+
+async function loadSnapshots() {
+    const status = document.querySelector('.snapshot-status');
+    const table = document.querySelector('.snapshot-list');
+    try {
+        const response = await apiFetch('/api/snapshots');
+        const data = await response.json();
+        if (data.status !== 'success') {
+            status.textContent = data.message || 'Failed to load snapshots';
+            return;
+        }
+        const body = table.querySelector('tbody');
+        body.innerHTML = '';
+        data.snapshots.forEach(snapshot => {
+            const row = document.createElement('tr');
+            row.innerHTML = `
+                <td>${escapeHtml(snapshot.id)}</td>
+                <td>${escapeHtml(snapshot.store)}</td>
+                <td>${escapeHtml(snapshot.created_at || '-')}</td>
+                <td>${snapshot.entry_count}</td>
+                <td>${formatSize(snapshot.total_size)}</td>
+                <td><code title="${snapshot.sha1}">${snapshot.sha1.slice(0, 12)}</code></td>
+                <td class="snapshot-actions"></td>
+            `;
+            const actions = row.querySelector('.snapshot-actions');
+            const dryRun = document.createElement('button');
+            dryRun.textContent = 'Dry run';
+            dryRun.addEventListener('click', () => restoreSnapshot(snapshot.id, true));
+            const restore = document.createElement('button');
+            restore.textContent = 'Restore';
+            restore.disabled = settings.read_only;
+            restore.addEventListener('click', () => restoreSnapshot(snapshot.id, false));
+            actions.append(dryRun, restore);
+            body.appendChild(row);
+        });
+        table.hidden = data.snapshots.length === 0;
+        if (data.snapshots.length === 0) status.textContent = 'No snapshots yet';
+    } catch (error) {
+        console.error('Error loading snapshots:', error);
+        status.textContent = 'Error loading snapshots';
+    }
+}
+
+async function takeSnapshot() {
+    const status = document.querySelector('.snapshot-status');
+    status.textContent = 'Taking snapshot...';
+    try {
+        const response = await apiFetch(storeApi('/snapshots'), { method: 'POST' });
+        const data = await response.json();
+        status.textContent = data.status === 'success'
+            ? `Snapshot ${data.snapshot.id} written (${data.snapshot.entry_count} entries)`
+            : `Snapshot failed: ${data.message}`;
+        loadSnapshots();
+    } catch (error) {
+        console.error('Error taking snapshot:', error);
+        status.textContent = 'Error taking snapshot';
+    }
+}
+
+// Replays a snapshot into the selected store, or only reports what that
+// would do when `dryRun` is set.
+async function restoreSnapshot(id, dryRun) {
+    const policy = document.getElementById('restore-policy').value;
+    if (!dryRun && !confirm(`Restore ${id} into ${currentStore ?? 'the default store'} (${policy})?`)) {
+        return;
+    }
+    const status = document.querySelector('.snapshot-status');
+    const report = document.querySelector('.restore-report');
+    status.textContent = dryRun ? 'Planning restore...' : 'Restoring...';
+    try {
+        const response = await apiFetch(storeApi('/restore'), {
+            method: 'POST',
+            headers: { 'Content-Type': 'application/json' },
+            body: JSON.stringify({ source: `snapshot:${id}`, dry_run: dryRun, policy }),
+        });
+        const data = await response.json();
+        if (data.code === 'restore_conflict') {
+            status.textContent = data.message;
+            report.textContent = `Conflicting keys:\n${data.conflicts.join('\n')}`;
+            report.hidden = false;
+            return;
+        }
+        if (data.status !== 'success') {
+            status.textContent = `Restore failed: ${data.message}`;
+            report.hidden = true;
+            return;
+        }
+        const s = data.summary;
+        status.textContent = dryRun
+            ? `${s.pending} would be written (${s.conflicts} conflicting), ${s.skipped} skipped`
+            : `${s.restored} restored, ${s.stored_under_different_key} stored under a different key, ` +
+              `${s.not_restored} unconfirmed, ${s.skipped} skipped` + (s.failed ? `, ${s.failed} failed` : '');
+        report.textContent = data.keys
+            .filter(k => k.outcome !== 'skipped' || k.reason !== 'identical value')
+            .map(k => `${k.outcome.padEnd(28)}${k.key}${k.reason ? ` (${k.reason})` : ''}` +
+                (k.stored_key ? ` -> stored as ${k.stored_key}` : ''))
+            .join('\n');
+        report.hidden = report.textContent === '';
+        if (!dryRun) reloadFromStart();
+    } catch (error) {
+        console.error('Error restoring snapshot:', error);
+        status.textContent = 'Error restoring snapshot';
+    }
+}
+
+// Downloads the entries matching the current filters. Goes through apiFetch
+// rather than a plain link so the access token is sent.
+async function exportEntries() {
+    const format = document.getElementById('export-format').value;
+    const params = new URLSearchParams({ ...searchFilters(), format });
+    try {
+        const response = await apiFetch(storeApi(`/export?${params}`));
+        if (!response.ok) {
+            const data = await response.json();
+            showListError(`Export failed: ${data.message}`);
+            return;
+        }
+        const disposition = response.headers.get('Content-Disposition') || '';
+        const match = disposition.match(/filename="([^"]+)"/);
+        const link = document.createElement('a');
+        link.href = URL.createObjectURL(await response.blob());
+        link.download = match ? match[1] : `store-export.${format}`;
+        link.click();
+        URL.revokeObjectURL(link.href);
+    } catch (error) {
+        console.error('Error exporting entries:', error);
+        showListError('Export failed');
+    }
+}
+
+async function importFile(file) {
+    const dryRun = document.getElementById('import-dry-run').checked;
+    const status = document.querySelector('.import-status');
+    const report = document.querySelector('.import-report');
+    const isNdjson = /\.(ndjson|jsonl)$/i.test(file.name);
+    status.textContent = `${dryRun ? 'Validating' : 'Importing'} ${file.name}...`;
+    try {
+        const response = await apiFetch(storeApi(`/import?dry_run=${dryRun}`), {
+            method: 'POST',
+            headers: { 'Content-Type': isNdjson ? 'application/x-ndjson' : 'application/json' },
+            body: await file.text(),
+        });
+        const data = await response.json();
+        if (data.status !== 'success') {
+            status.textContent = `Import failed: ${data.message}`;
+            report.hidden = true;
+            return;
+        }
+        const s = data.summary;
+        status.textContent = dryRun
+            ? `${s.valid} of ${s.records} records valid, ${s.invalid} invalid`
+            : `${s.written} written, ${s.stored_under_different_key} stored under a different key, ` +
+                `${s.unconfirmed} unconfirmed, ${s.invalid} invalid, ${s.failed} failed` +
+                (s.not_attempted ? `, ${s.not_attempted} not attempted` : '');
+        report.textContent = data.keys
+            .map(k => `#${k.record} ${k.outcome.padEnd(28)}${k.key}` +
+                (k.error ? ` (${k.error})` : '') +
+                (k.assigned_key && k.assigned_key !== k.key ? ` -> stored as ${k.assigned_key}` : ''))
+            .join('\n');
+        report.hidden = false;
+        if (!dryRun) reloadFromStart();
+    } catch (error) {
+        console.error('Error importing file:', error);
+        status.textContent = 'Error importing file';
+    }
+}
+
+function clearQuery() {
+    document.getElementById('query-input').value = '';
+    document.querySelector('.query-status').textContent = '';
+    document.querySelector('.query-results').hidden = true;
+}
+
+// The actor's own event chain. Listings leave out the event data; clicking
+// an event loads and shows it.
+let chainNextOffset = null;
+
+async function loadChain(append = false) {
+    const status = document.querySelector('.chain-status');
+    const timeline = document.querySelector('.chain-timeline');
+    const more = document.getElementById('chain-more');
+    const typeSelect = document.getElementById('chain-type');
+    const params = new URLSearchParams({
+        order: document.getElementById('chain-order').value,
+        offset: append ? chainNextOffset : 0,
+        data: false,
+    });
+    if (typeSelect.value) params.set('type', typeSelect.value);
+    status.textContent = 'Loading...';
+    try {
+        const response = await apiFetch(`/api/chain?${params}`);
+        const data = await response.json();
+        if (data.status !== 'success') {
+            status.textContent = data.message || 'Failed to load the event chain';
+            return;
+        }
+        const selected = typeSelect.value;
+        typeSelect.innerHTML = '<option value="">All event types</option>' +
+            Object.entries(data.types)
+                .map(([type, count]) => `<option value="${escapeHtml(type)}">${escapeHtml(type)} (${count})</option>`)
+                .join('');
+        typeSelect.value = selected;
+
+        if (!append) timeline.innerHTML = '';
+        data.events.forEach(event => timeline.appendChild(renderChainEvent(event)));
+        chainNextOffset = data.next_offset;
+        more.hidden = chainNextOffset === null;
+        status.textContent = `${timeline.children.length} of ${data.total} events (chain length ${data.length})`;
+    } catch (error) {
+        console.error('Error loading event chain:', error);
+        status.textContent = 'Error loading the event chain';
+    }
+}
+
+function renderChainEvent(event) {
+    const item = document.createElement('li');
+    item.className = 'chain-event';
+    item.innerHTML = `
+        <span class="event-meta">#${event.index}</span>
+        <span class="event-type">${escapeHtml(event.event_type)}</span>
+        <span class="event-meta">
+            <code title="${event.hash}">${event.hash.slice(0, 8)}</code>
+            ${event.parent ? `&larr; <code title="${event.parent}">${event.parent.slice(0, 8)}</code>` : '(root)'}
+            &middot; ${formatSize(event.size)}
+        </span>
+    `;
+    item.addEventListener('click', async (e) => {
+        if (e.target.closest('pre')) return;
+        const open = item.querySelector('pre');
+        if (open) {
+            open.remove();
+            return;
+        }
+        const pre = document.createElement('pre');
+        pre.textContent = 'Loading...';
+        item.appendChild(pre);
+        try {
+            const response = await apiFetch(`/api/chain/${event.hash}`);
+            const data = await response.json();
+            if (data.status !== 'success') {
+                pre.textContent = data.message;
+                return;
+            }
+            const full = data.event;
+            pre.textContent = full.encoding === 'json'
+                ? JSON.stringify(full.data, null, 2)
+                : `(${full.encoding})\n${full.data}`;
+        } catch (error) {
+            console.error('Error loading event:', error);
+            pre.textContent = 'Error loading event';
+        }
+    });
+    return item;
+}
+
+// Event Listeners
+document.getElementById('new-entry').addEventListener('click', () => openEditor(null, '{\n}'));
+document.getElementById('run-query').addEventListener('click', runQuery);
+document.getElementById('run-diff').addEventListener('click', runDiff);
+document.getElementById('take-snapshot').addEventListener('click', takeSnapshot);
+const dropZone = document.querySelector('.drop-zone');
+const importInput = document.getElementById('import-file');
+dropZone.addEventListener('click', () => importInput.click());
+importInput.addEventListener('change', () => {
+    if (importInput.files.length > 0) importFile(importInput.files[0]);
+    importInput.value = '';
+});
+dropZone.addEventListener('dragover', (e) => {
+    e.preventDefault();
+    dropZone.classList.add('dragging');
+});
+dropZone.addEventListener('dragleave', () => dropZone.classList.remove('dragging'));
+dropZone.addEventListener('drop', (e) => {
+    e.preventDefault();
+    dropZone.classList.remove('dragging');
+    if (e.dataTransfer.files.length > 0) importFile(e.dataTransfer.files[0]);
+});
+document.querySelector('.snapshots-panel').addEventListener('toggle', (e) => {
+    if (e.target.open) loadSnapshots();
+});
+document.querySelector('.chain-panel').addEventListener('toggle', (e) => {
+    if (e.target.open) loadChain();
+});
+document.getElementById('load-chain').addEventListener('click', () => loadChain());
+document.getElementById('chain-type').addEventListener('change', () => loadChain());
+document.getElementById('chain-order').addEventListener('change', () => loadChain());
+document.getElementById('chain-more').addEventListener('click', () => loadChain(true));
+document.getElementById('clear-query').addEventListener('click', clearQuery);
+document.getElementById('query-input').addEventListener('keydown', (e) => {
+    if (e.key === 'Enter') runQuery();
+});
+document.getElementById('refresh').addEventListener('click', reloadFromStart);
+document.getElementById('export').addEventListener('click', exportEntries);
+document.getElementById('store-select').addEventListener('change', (e) => switchStore(e.target.value));
+document.getElementById('prev-page').addEventListener('click', previousPage);
+document.getElementById('next-page').addEventListener('click', nextPage);
+document.getElementById('sort').addEventListener('change', (e) => {
+    sortBy = e.target.value;
+    reloadFromStart();
+});
+document.getElementById('order').addEventListener('change', (e) => {
+    sortOrder = e.target.value;
+    reloadFromStart();
+});
+['search', 'filter-contains', 'filter-min-size', 'filter-max-size'].forEach(id =>
+    document.getElementById(id).addEventListener('input', scheduleSearch)
+);
+['search-mode', 'filter-type'].forEach(id =>
+    document.getElementById(id).addEventListener('change', reloadFromStart)
+);
+
+async function copyToClipboard(text, button = document.querySelector('.action-buttons button')) {
+    try {
+        await navigator.clipboard.writeText(text);
+        const label = button.textContent;
+        button.textContent = 'Copied!';
+        button.classList.add('success');
+        setTimeout(() => {
+            button.textContent = label;
+            button.classList.remove('success');
+        }, 2000);
+    } catch (err) {
+        console.error('Failed to copy text: ', err);
+    }
+}
+
+// Live updates. The websocket interface only answers messages, so after
+// subscribing we poll over the socket; each poll returns the changes since
+// the last sequence number we saw.
+let liveSocket = null;
+let liveVersion = 0;
+let livePollTimer = null;
+let liveRetryTimer = null;
+let liveRefreshTimer = null;
+let liveChangeCount = 0;
+
+function liveUrl() {
+    if (settings.live && settings.live.websocket_url) return settings.live.websocket_url;
+    const scheme = location.protocol === 'https:' ? 'wss' : 'ws';
+    return `${scheme}://${location.hostname}:8091`;
+}
+
+function setLiveStatus(text, connected) {
+    const status = document.querySelector('.live-status');
+    status.textContent = text;
+    status.title = text;
+    status.classList.toggle('connected', connected);
+}
+
+function sendLive(message) {
+    if (!liveSocket || liveSocket.readyState !== WebSocket.OPEN) return;
+    const token = localStorage.getItem(TOKEN_KEY);
+    liveSocket.send(JSON.stringify({ ...message, store: currentStore, ...(token ? { token } : {}) }));
+}
+
+// Reloads the current page shortly after a burst of changes.
+function scheduleLiveRefresh() {
+    clearTimeout(liveRefreshTimer);
+    liveRefreshTimer = setTimeout(() => fetchStoreContents(currentCursor), 500);
+}
+
+function applyChange(change) {
+    liveChangeCount += 1;
+    liveVersion = Math.max(liveVersion, change.seq);
+    const verb = change.type.replace('entry_', '');
+    setLiveStatus(`Live: ${liveChangeCount} changes (${change.key} ${verb})`, true);
+    scheduleLiveRefresh();
+    if (selectedEntry && selectedEntry.key === change.key) {
+        if (change.type === 'entry_removed') {
+            document.querySelector('.details-content').innerHTML = '<p>This entry was removed</p>';
+        } else {
+            showDetails(selectedEntry);
+        }
+    }
+    setTimeout(() => {
+        document.querySelectorAll('.entry').forEach(el => {
+            if (el.querySelector('strong').textContent === change.key) el.classList.add('flash');
+        });
+    }, 600);
+}
+
+function handleLiveMessage(event) {
+    const message = JSON.parse(event.data);
+    switch (message.type) {
+        case 'subscribed':
+            liveVersion = message.version;
+            setLiveStatus('Live', true);
+            clearInterval(livePollTimer);
+            livePollTimer = setInterval(
+                () => sendLive({ type: 'poll', since: liveVersion }),
+                (settings.live && settings.live.poll_interval_ms) || 2000
+            );
+            break;
+        case 'entry_added':
+        case 'entry_changed':
+        case 'entry_removed':
+            applyChange(message);
+            break;
+        case 'synced':
+            liveVersion = message.version;
+            break;
+        case 'resync':
+            liveVersion = message.version;
+            scheduleLiveRefresh();
+            break;
+        case 'error':
+            console.error('Live update error:', message);
+            setLiveStatus(`Live: ${message.message}`, false);
+            break;
+    }
+}
+
+function connectLive() {
+    disconnectLive();
+    if (!document.getElementById('live').checked) return;
+    try {
+        liveSocket = new WebSocket(liveUrl());
+    } catch (error) {
+        console.error('Error opening websocket:', error);
+        setLiveStatus('Live updates unavailable', false);
+        return;
+    }
+    liveSocket.addEventListener('open', () => sendLive({ type: 'subscribe' }));
+    liveSocket.addEventListener('message', handleLiveMessage);
+    liveSocket.addEventListener('close', () => {
+        clearInterval(livePollTimer);
+        if (!document.getElementById('live').checked) return;
+        setLiveStatus('Live: reconnecting...', false);
+        liveRetryTimer = setTimeout(connectLive, 5000);
+    });
+}
+
+function disconnectLive() {
+    clearInterval(livePollTimer);
+    clearTimeout(liveRetryTimer);
+    if (liveSocket) {
+        const socket = liveSocket;
+        liveSocket = null;
+        socket.close();
+    }
+    liveChangeCount = 0;
+    setLiveStatus('', false);
+}
+
+document.getElementById('live').addEventListener('change', connectLive);
+
+// Initial load
+loadStores().then(loadSettings).then(() => {
+    reloadFromStart();
+    connectLive();
+});
\ No newline at end of file
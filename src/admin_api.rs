@@ -0,0 +1,179 @@
+//! Runtime reconfiguration, shared by the HTTP admin endpoint and the
+//! message-server protocol, and the entry cache's statistics.
+
+use crate::bindings::exports::ntwk::theater::http_server::HttpResponse;
+use crate::bindings::ntwk::theater::runtime::log;
+use crate::config::Config;
+use crate::router::RequestContext;
+use crate::{cache, response, State};
+use serde_json::{json, Value};
+use std::fmt;
+
+/// Why a configuration change was refused.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UpdateError {
+    /// No `auth.token` is configured, so no caller can be trusted with
+    /// anything but switching read-only mode on.
+    Disabled,
+    /// The patch changes a setting that only `init.json` may set.
+    Locked(&'static str),
+    /// The patched configuration fails validation.
+    Invalid(String),
+}
+
+impl UpdateError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            UpdateError::Disabled | UpdateError::Locked(_) => "forbidden",
+            UpdateError::Invalid(_) => "invalid_config",
+        }
+    }
+
+    pub fn into_response(self) -> HttpResponse {
+        let status = match self {
+            UpdateError::Disabled | UpdateError::Locked(_) => 403,
+            UpdateError::Invalid(_) => 422,
+        };
+        response::error_with_code(status, self.code(), &self.to_string())
+    }
+}
+
+impl fmt::Display for UpdateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UpdateError::Disabled => {
+                f.write_str("Only {\"read_only\": true} is accepted while auth.token is unset")
+            }
+            UpdateError::Locked(field) => {
+                write!(f, "{} can only be set in the init configuration", field)
+            }
+            UpdateError::Invalid(e) => f.write_str(e),
+        }
+    }
+}
+
+/// Applies `patch` to `config`. Without a token anyone may switch read-only
+/// mode on, the safe direction, but nothing else; token holders may change
+/// everything except the token itself.
+fn checked_patch(config: &Config, patch: &Value) -> Result<Config, UpdateError> {
+    if !config.auth.enabled() && *patch != json!({ "read_only": true }) {
+        return Err(UpdateError::Disabled);
+    }
+    let patched = config.patched(patch).map_err(UpdateError::Invalid)?;
+    if patched.auth.token != config.auth.token {
+        return Err(UpdateError::Locked("auth"));
+    }
+    Ok(patched)
+}
+
+/// Validates `patch` against the current configuration and, if it passes,
+/// makes it the configuration of `state`. Returns the redacted result.
+/// Callers must have checked the caller's token.
+pub fn update_config(state: &mut State, patch: &Value) -> Result<Value, UpdateError> {
+    let config = checked_patch(&state.config, patch)?;
+    if config.registry() != state.config.registry()
+        || config.default_store_name() != state.config.default_store_name()
+    {
+        log(&format!(
+            "Store registry changed: {:?} (default {})",
+            config.registry(),
+            config.default_store_name()
+        ));
+    }
+    state.config = config;
+    let effective = state.config.redacted();
+    log(&format!("Configuration updated: {}", effective));
+    Ok(effective)
+}
+
+/// `GET /api/admin/config`
+pub fn get_config(_: &RequestContext, state: &mut State) -> HttpResponse {
+    response::success(json!({ "config": state.config.redacted() }))
+}
+
+/// `PATCH /api/admin/config` with a JSON merge patch as the body.
+pub fn patch_config(req: &RequestContext, state: &mut State) -> HttpResponse {
+    let patch: Value = match serde_json::from_slice(req.body) {
+        Ok(patch) => patch,
+        Err(e) => return response::error(400, &format!("Invalid JSON: {}", e)),
+    };
+    match update_config(state, &patch) {
+        Ok(config) => response::success(json!({ "config": config })),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// `GET /api/admin/cache`: hit/miss counters and the cached stores.
+pub fn get_cache(_: &RequestContext, state: &mut State) -> HttpResponse {
+    let cache = cache::with(|c| c.to_json(state.clock, &state.config.cache));
+    response::success(json!({ "cache": cache }))
+}
+
+/// `DELETE /api/admin/cache`: drops every cached store.
+pub fn clear_cache(_: &RequestContext, state: &mut State) -> HttpResponse {
+    let cache = cache::with(|c| {
+        c.clear();
+        c.to_json(state.clock, &state.config.cache)
+    });
+    log("Entry cache cleared");
+    response::success(json!({ "cache": cache }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::config;
+
+    #[test]
+    fn without_auth_only_enables_read_only() {
+        let current = config(r#"{"store_id": "kv"}"#);
+        let patched = checked_patch(&current, &json!({ "read_only": true })).unwrap();
+        assert!(patched.read_only);
+
+        let read_only = config(r#"{"store_id": "kv", "read_only": true}"#);
+        for patch in [
+            json!({ "read_only": false }),
+            json!({ "read_only": true, "page_size": 10 }),
+            json!({ "page_size": 10 }),
+        ] {
+            assert_eq!(
+                checked_patch(&read_only, &patch).unwrap_err(),
+                UpdateError::Disabled
+            );
+        }
+    }
+
+    #[test]
+    fn token_holders_toggle_read_only() {
+        let current = config(r#"{"store_id": "kv", "read_only": true, "auth": {"token": "t"}}"#);
+        let off = checked_patch(&current, &json!({ "read_only": false })).unwrap();
+        assert!(!off.read_only);
+        let on = checked_patch(&off, &json!({ "read_only": true })).unwrap();
+        assert!(on.read_only);
+    }
+
+    #[test]
+    fn refuses_changes_to_the_token() {
+        let current = config(r#"{"store_id": "kv", "auth": {"token": "t"}}"#);
+        for patch in [
+            json!({ "auth": { "token": "other" } }),
+            json!({ "auth": null }),
+        ] {
+            assert_eq!(
+                checked_patch(&current, &patch).unwrap_err(),
+                UpdateError::Locked("auth")
+            );
+        }
+    }
+
+    #[test]
+    fn applies_other_changes() {
+        let current = config(r#"{"store_id": "kv", "auth": {"token": "t"}}"#);
+        let patched = checked_patch(&current, &json!({ "page_size": 10 })).unwrap();
+        assert_eq!(patched.page_size, 10);
+        assert!(matches!(
+            checked_patch(&current, &json!({ "page_size": 0 })),
+            Err(UpdateError::Invalid(_))
+        ));
+    }
+}
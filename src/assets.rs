@@ -0,0 +1,293 @@
+//! Serves UI files from the `filesystem` handler.
+//!
+//! The handler's root also holds `init.json` and the snapshots, so only the
+//! `public` directory below it is served by default, and only files of the
+//! types a web UI is made of. Data and configuration files are never served,
+//! whatever `assets.root` points at.
+
+use crate::bindings::exports::ntwk::theater::http_server::HttpResponse;
+use crate::bindings::ntwk::theater::filesystem::{path_exists, read_file};
+use crate::bindings::ntwk::theater::runtime::log;
+use crate::response;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct AssetConfig {
+    /// URL prefix the assets are served under, e.g. `/` or `/ui`.
+    pub prefix: String,
+    /// File served for the prefix itself and for directories.
+    pub index_file: String,
+    /// Directory, relative to the filesystem handler's root, holding the
+    /// files.
+    pub root: String,
+}
+
+impl Default for AssetConfig {
+    fn default() -> Self {
+        AssetConfig {
+            prefix: "/".to_string(),
+            index_file: "index.html".to_string(),
+            root: "public".to_string(),
+        }
+    }
+}
+
+/// Content-Type for a file name, based on its extension.
+pub fn mime_type(path: &str) -> &'static str {
+    let extension = path
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "html" | "htm" => "text/html; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "js" | "mjs" => "application/javascript; charset=utf-8",
+        "json" | "map" => "application/json",
+        "txt" => "text/plain; charset=utf-8",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "ico" => "image/x-icon",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        "wasm" => "application/wasm",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Returns the path segments below the configured prefix, or `None` if the
+/// request is outside of it.
+fn strip_prefix<'a>(path: &'a [String], prefix: &str) -> Option<&'a [String]> {
+    let prefix: Vec<&str> = prefix.split('/').filter(|s| !s.is_empty()).collect();
+    if path.len() < prefix.len() || path.iter().zip(&prefix).any(|(a, b)| a != b) {
+        return None;
+    }
+    Some(&path[prefix.len()..])
+}
+
+/// Extensions of the files that may be served.
+const SERVED_EXTENSIONS: &[&str] = &[
+    "html", "htm", "css", "js", "mjs", "svg", "png", "jpg", "jpeg", "gif", "webp", "ico", "woff",
+    "woff2", "ttf",
+];
+
+fn is_served_file(path: &str) -> bool {
+    path.rsplit('/')
+        .next()
+        .and_then(|name| name.rsplit_once('.'))
+        .is_some_and(|(_, ext)| SERVED_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+fn is_safe_segment(segment: &str) -> bool {
+    segment != ".." && segment != "." && !segment.contains(['/', '\\', '\0'])
+}
+
+fn load(path: &str) -> Option<Vec<u8>> {
+    match path_exists(path) {
+        Ok(true) => {}
+        Ok(false) => return None,
+        Err(e) => {
+            log(&format!("path_exists({}) failed: {}", path, e));
+            return None;
+        }
+    }
+    read_file(path)
+        .map_err(|e| log(&format!("read_file({}) failed: {}", path, e)))
+        .ok()
+}
+
+#[derive(Debug, PartialEq)]
+enum Lookup {
+    /// Not below the asset prefix.
+    Outside,
+    /// Below the prefix, but not a file that may be served.
+    Refused,
+    /// Paths to try, relative to the filesystem handler's root.
+    Files(Vec<String>),
+}
+
+/// Whether `file` lies in one of the `private` directories.
+fn is_private(file: &str, private: &[&str]) -> bool {
+    private.iter().any(|dir| {
+        let dir = dir.trim_matches('/');
+        !dir.is_empty()
+            && file
+                .strip_prefix(dir)
+                .is_some_and(|rest| rest.starts_with('/'))
+    })
+}
+
+/// Finds the files `path` may be served from. Nothing in the `private`
+/// directories (relative to the filesystem handler's root) is served.
+fn lookup(path: &[String], config: &AssetConfig, private: &[&str]) -> Lookup {
+    let Some(relative) = strip_prefix(path, &config.prefix) else {
+        return Lookup::Outside;
+    };
+    if !relative.iter().all(|s| is_safe_segment(s)) {
+        return Lookup::Refused;
+    }
+
+    let root = config.root.trim_matches('/');
+    let file = relative.join("/");
+    // Names with an extension are files; others may be directories.
+    let is_file = relative.last().is_some_and(|name| name.contains('.'));
+    let candidates = if file.is_empty() {
+        vec![config.index_file.clone()]
+    } else if is_file {
+        vec![file]
+    } else {
+        vec![format!("{}/{}", file, config.index_file)]
+    };
+    let files: Vec<String> = candidates
+        .into_iter()
+        .filter(|candidate| is_served_file(candidate))
+        .map(|candidate| {
+            if root.is_empty() {
+                candidate
+            } else {
+                format!("{}/{}", root, candidate)
+            }
+        })
+        .filter(|file| !is_private(file, private))
+        .collect();
+    if files.is_empty() {
+        Lookup::Refused
+    } else {
+        Lookup::Files(files)
+    }
+}
+
+/// Serves the file at `path` if it lies under the asset prefix and outside
+/// the `private` directories. Returns `None` when the path is not an asset
+/// path at all so the caller can fall through.
+pub fn serve(path: &[String], config: &AssetConfig, private: &[&str]) -> Option<HttpResponse> {
+    let files = match lookup(path, config, private) {
+        Lookup::Outside => return None,
+        Lookup::Refused => return Some(response::error(403, "Forbidden")),
+        Lookup::Files(files) => files,
+    };
+    for file in files {
+        if let Some(content) = load(&file) {
+            return Some(HttpResponse {
+                status: 200,
+                headers: vec![("Content-Type".to_string(), mime_type(&file).to_string())],
+                body: Some(content),
+            });
+        }
+    }
+    Some(response::not_found())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::router::split_uri;
+
+    fn lookup_uri(uri: &str, config: &AssetConfig) -> Lookup {
+        lookup(&split_uri(uri).0, config, &["snapshots"])
+    }
+
+    fn files(files: &[&str]) -> Lookup {
+        Lookup::Files(files.iter().map(|f| f.to_string()).collect())
+    }
+
+    #[test]
+    fn serves_ui_files_from_the_public_dir() {
+        let config = AssetConfig::default();
+        assert_eq!(lookup_uri("/", &config), files(&["public/index.html"]));
+        assert_eq!(
+            lookup_uri("/visualizer.js", &config),
+            files(&["public/visualizer.js"])
+        );
+        assert_eq!(
+            lookup_uri("/docs", &config),
+            files(&["public/docs/index.html"])
+        );
+    }
+
+    #[test]
+    fn never_serves_config_or_data_files() {
+        // Even with the whole filesystem handler root published.
+        let config = AssetConfig {
+            root: String::new(),
+            ..AssetConfig::default()
+        };
+        for uri in [
+            "/init.json",
+            "/INIT.JSON",
+            "/snapshots/default-20240501T123000Z/entries.json",
+            "/snapshots/default-20240501T123000Z/manifest.json",
+            "/export.csv",
+        ] {
+            assert_eq!(lookup_uri(uri, &config), Lookup::Refused, "{}", uri);
+        }
+    }
+
+    #[test]
+    fn never_serves_the_snapshots_dir() {
+        let config = AssetConfig {
+            root: String::new(),
+            ..AssetConfig::default()
+        };
+        assert_eq!(
+            lookup_uri("/snapshots/s-20240501T123000Z/index.html", &config),
+            Lookup::Refused
+        );
+        assert_eq!(lookup_uri("/snapshots", &config), Lookup::Refused);
+        assert_eq!(
+            lookup_uri("/snapshots-help.html", &config),
+            files(&["snapshots-help.html"])
+        );
+    }
+
+    #[test]
+    fn safe_segments() {
+        for segment in ["a.js", "..a", "a..b", ".well-known"] {
+            assert!(is_safe_segment(segment), "{}", segment);
+        }
+        for segment in ["..", ".", "a/b", "a\\b", "a\0b"] {
+            assert!(!is_safe_segment(segment), "{:?}", segment);
+        }
+    }
+
+    #[test]
+    fn refuses_traversal() {
+        let config = AssetConfig::default();
+        for uri in [
+            "/../init.json",
+            "/%2e%2e/init.json",
+            "/%2E%2E/%2e%2E/etc/passwd.html",
+            "/a/%2e%2e%2f%2e%2e%2findex.html",
+            "/..%5cinit.json",
+            "/./index.html",
+        ] {
+            assert_eq!(lookup_uri(uri, &config), Lookup::Refused, "{}", uri);
+        }
+    }
+
+    #[test]
+    fn empty_segments_are_ignored() {
+        let config = AssetConfig::default();
+        assert_eq!(
+            lookup_uri("//css//styles.css", &config),
+            files(&["public/css/styles.css"])
+        );
+    }
+
+    #[test]
+    fn prefix_must_match_whole_segments() {
+        let config = AssetConfig {
+            prefix: "/ui/".to_string(),
+            ..AssetConfig::default()
+        };
+        assert_eq!(lookup_uri("/ui", &config), files(&["public/index.html"]));
+        assert_eq!(lookup_uri("/ui/app.js", &config), files(&["public/app.js"]));
+        assert_eq!(lookup_uri("/uix/app.js", &config), Lookup::Outside);
+        assert_eq!(lookup_uri("/app.js", &config), Lookup::Outside);
+        assert_eq!(strip_prefix(&[], "/ui"), None);
+    }
+}
@@ -0,0 +1,48 @@
+//! Optional bearer-token protection for the API.
+
+use crate::bindings::exports::ntwk::theater::http_server::HttpResponse;
+use crate::response;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthConfig {
+    /// When set, `/api/` requests must send `Authorization: Bearer <token>`.
+    pub token: Option<String>,
+}
+
+impl AuthConfig {
+    pub fn enabled(&self) -> bool {
+        self.token.is_some()
+    }
+
+    /// Whether `token` grants access. Anything does when no token is set.
+    pub fn accepts(&self, token: Option<&str>) -> bool {
+        match &self.token {
+            None => true,
+            Some(expected) => token.is_some_and(|token| constant_time_eq(token.trim(), expected)),
+        }
+    }
+
+    /// Checks the `Authorization` header, returning the 401 to send on failure.
+    pub fn check(&self, authorization: Option<&str>) -> Result<(), HttpResponse> {
+        if self.accepts(authorization.and_then(|value| value.strip_prefix("Bearer "))) {
+            return Ok(());
+        }
+        let mut response =
+            response::error_with_code(401, "unauthorized", "Missing or invalid token");
+        response.headers.push((
+            "WWW-Authenticate".to_string(),
+            "Bearer realm=\"store-visualizer\"".to_string(),
+        ));
+        Err(response)
+    }
+}
+
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+            == 0
+}
@@ -1,4 +1,4 @@
-// Generated by `wit-bindgen` 0.36.0. DO NOT EDIT!
+// Generated by `wit-bindgen` 0.41.0. DO NOT EDIT!
 // Options used:
 //   * runtime_path: "wit_bindgen_rt"
 #[rustfmt::skip]
@@ -6,7 +6,7 @@
 pub mod ntwk {
     pub mod theater {
         /// Define a shared type for messages
-        #[allow(dead_code, clippy::all)]
+        #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
         pub mod types {
             #[used]
             #[doc(hidden)]
@@ -63,7 +63,7 @@ pub mod ntwk {
                 }
             }
         }
-        #[allow(dead_code, clippy::all)]
+        #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
         pub mod runtime {
             #[used]
             #[doc(hidden)]
@@ -71,88 +71,116 @@ pub mod ntwk {
             use super::super::super::_rt;
             pub type Chain = super::super::super::ntwk::theater::types::Chain;
             #[allow(unused_unsafe, clippy::all)]
-            pub fn log(msg: &str) {
+            pub fn log(msg: &str) -> () {
                 unsafe {
                     let vec0 = msg;
                     let ptr0 = vec0.as_ptr().cast::<u8>();
                     let len0 = vec0.len();
                     #[cfg(target_arch = "wasm32")]
                     #[link(wasm_import_module = "ntwk:theater/runtime")]
-                    extern "C" {
+                    unsafe extern "C" {
                         #[link_name = "log"]
-                        fn wit_import(_: *mut u8, _: usize);
+                        fn wit_import1(_: *mut u8, _: usize);
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(_: *mut u8, _: usize) {
+                    unsafe extern "C" fn wit_import1(_: *mut u8, _: usize) {
                         unreachable!()
                     }
-                    wit_import(ptr0.cast_mut(), len0);
+                    unsafe { wit_import1(ptr0.cast_mut(), len0) };
                 }
             }
             #[allow(unused_unsafe, clippy::all)]
             pub fn get_chain() -> Chain {
                 unsafe {
-                    #[repr(align(4))]
-                    struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
-                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 2 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 2
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
                     let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
                     #[link(wasm_import_module = "ntwk:theater/runtime")]
-                    extern "C" {
+                    unsafe extern "C" {
                         #[link_name = "get-chain"]
-                        fn wit_import(_: *mut u8);
+                        fn wit_import1(_: *mut u8);
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(_: *mut u8) {
+                    unsafe extern "C" fn wit_import1(_: *mut u8) {
                         unreachable!()
                     }
-                    wit_import(ptr0);
-                    let l1 = *ptr0.add(0).cast::<*mut u8>();
-                    let l2 = *ptr0.add(4).cast::<usize>();
-                    let base12 = l1;
-                    let len12 = l2;
-                    let mut result12 = _rt::Vec::with_capacity(len12);
-                    for i in 0..len12 {
-                        let base = base12.add(i * 40);
-                        let e12 = {
-                            let l3 = *base.add(0).cast::<i64>();
-                            let l4 = *base.add(8).cast::<*mut u8>();
-                            let l5 = *base.add(12).cast::<usize>();
-                            let len6 = l5;
-                            let bytes6 = _rt::Vec::from_raw_parts(l4.cast(), len6, len6);
-                            let l7 = i32::from(*base.add(16).cast::<u8>());
-                            let l9 = *base.add(32).cast::<*mut u8>();
-                            let l10 = *base.add(36).cast::<usize>();
-                            let len11 = l10;
+                    unsafe { wit_import1(ptr0) };
+                    let l2 = *ptr0.add(0).cast::<*mut u8>();
+                    let l3 = *ptr0
+                        .add(::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let base13 = l2;
+                    let len13 = l3;
+                    let mut result13 = _rt::Vec::with_capacity(len13);
+                    for i in 0..len13 {
+                        let base = base13
+                            .add(i * (24 + 4 * ::core::mem::size_of::<*const u8>()));
+                        let e13 = {
+                            let l4 = *base.add(0).cast::<i64>();
+                            let l5 = *base.add(8).cast::<*mut u8>();
+                            let l6 = *base
+                                .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let len7 = l6;
+                            let bytes7 = _rt::Vec::from_raw_parts(l5.cast(), len7, len7);
+                            let l8 = i32::from(
+                                *base
+                                    .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<u8>(),
+                            );
+                            let l10 = *base
+                                .add(24 + 2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l11 = *base
+                                .add(24 + 3 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let len12 = l11;
                             super::super::super::ntwk::theater::types::MetaEvent {
-                                hash: l3 as u64,
+                                hash: l4 as u64,
                                 event: super::super::super::ntwk::theater::types::Event {
-                                    event_type: _rt::string_lift(bytes6),
-                                    parent: match l7 {
+                                    event_type: _rt::string_lift(bytes7),
+                                    parent: match l8 {
                                         0 => None,
                                         1 => {
                                             let e = {
-                                                let l8 = *base.add(24).cast::<i64>();
-                                                l8 as u64
+                                                let l9 = *base
+                                                    .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<i64>();
+                                                l9 as u64
                                             };
                                             Some(e)
                                         }
                                         _ => _rt::invalid_enum_discriminant(),
                                     },
-                                    data: _rt::Vec::from_raw_parts(l9.cast(), len11, len11),
+                                    data: _rt::Vec::from_raw_parts(l10.cast(), len12, len12),
                                 },
                             }
                         };
-                        result12.push(e12);
-                    }
-                    _rt::cabi_dealloc(base12, len12 * 40, 8);
-                    super::super::super::ntwk::theater::types::Chain {
-                        events: result12,
+                        result13.push(e13);
                     }
+                    _rt::cabi_dealloc(
+                        base13,
+                        len13 * (24 + 4 * ::core::mem::size_of::<*const u8>()),
+                        8,
+                    );
+                    let result14 = super::super::super::ntwk::theater::types::Chain {
+                        events: result13,
+                    };
+                    result14
                 }
             }
         }
-        #[allow(dead_code, clippy::all)]
+        #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
         pub mod filesystem {
             #[used]
             #[doc(hidden)]
@@ -161,59 +189,84 @@ pub mod ntwk {
             #[allow(unused_unsafe, clippy::all)]
             pub fn read_file(path: &str) -> Result<_rt::Vec<u8>, _rt::String> {
                 unsafe {
-                    #[repr(align(4))]
-                    struct RetArea([::core::mem::MaybeUninit<u8>; 12]);
-                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 12]);
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
                     let vec0 = path;
                     let ptr0 = vec0.as_ptr().cast::<u8>();
                     let len0 = vec0.len();
                     let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
                     #[link(wasm_import_module = "ntwk:theater/filesystem")]
-                    extern "C" {
+                    unsafe extern "C" {
                         #[link_name = "read-file"]
-                        fn wit_import(_: *mut u8, _: usize, _: *mut u8);
+                        fn wit_import2(_: *mut u8, _: usize, _: *mut u8);
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(_: *mut u8, _: usize, _: *mut u8) {
+                    unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8) {
                         unreachable!()
                     }
-                    wit_import(ptr0.cast_mut(), len0, ptr1);
-                    let l2 = i32::from(*ptr1.add(0).cast::<u8>());
-                    match l2 {
+                    unsafe { wit_import2(ptr0.cast_mut(), len0, ptr1) };
+                    let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+                    let result10 = match l3 {
                         0 => {
                             let e = {
-                                let l3 = *ptr1.add(4).cast::<*mut u8>();
-                                let l4 = *ptr1.add(8).cast::<usize>();
-                                let len5 = l4;
-                                _rt::Vec::from_raw_parts(l3.cast(), len5, len5)
+                                let l4 = *ptr1
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l5 = *ptr1
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len6 = l5;
+                                _rt::Vec::from_raw_parts(l4.cast(), len6, len6)
                             };
                             Ok(e)
                         }
                         1 => {
                             let e = {
-                                let l6 = *ptr1.add(4).cast::<*mut u8>();
-                                let l7 = *ptr1.add(8).cast::<usize>();
-                                let len8 = l7;
-                                let bytes8 = _rt::Vec::from_raw_parts(
-                                    l6.cast(),
-                                    len8,
-                                    len8,
+                                let l7 = *ptr1
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l8 = *ptr1
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len9 = l8;
+                                let bytes9 = _rt::Vec::from_raw_parts(
+                                    l7.cast(),
+                                    len9,
+                                    len9,
                                 );
-                                _rt::string_lift(bytes8)
+                                _rt::string_lift(bytes9)
                             };
                             Err(e)
                         }
                         _ => _rt::invalid_enum_discriminant(),
-                    }
+                    };
+                    result10
                 }
             }
             #[allow(unused_unsafe, clippy::all)]
             pub fn write_file(path: &str, content: &str) -> Result<(), _rt::String> {
                 unsafe {
-                    #[repr(align(4))]
-                    struct RetArea([::core::mem::MaybeUninit<u8>; 12]);
-                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 12]);
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
                     let vec0 = path;
                     let ptr0 = vec0.as_ptr().cast::<u8>();
                     let len0 = vec0.len();
@@ -223,9 +276,9 @@ pub mod ntwk {
                     let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
                     #[link(wasm_import_module = "ntwk:theater/filesystem")]
-                    extern "C" {
+                    unsafe extern "C" {
                         #[link_name = "write-file"]
-                        fn wit_import(
+                        fn wit_import3(
                             _: *mut u8,
                             _: usize,
                             _: *mut u8,
@@ -234,7 +287,7 @@ pub mod ntwk {
                         );
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(
+                    unsafe extern "C" fn wit_import3(
                         _: *mut u8,
                         _: usize,
                         _: *mut u8,
@@ -243,268 +296,1405 @@ pub mod ntwk {
                     ) {
                         unreachable!()
                     }
-                    wit_import(ptr0.cast_mut(), len0, ptr1.cast_mut(), len1, ptr2);
-                    let l3 = i32::from(*ptr2.add(0).cast::<u8>());
-                    match l3 {
+                    unsafe {
+                        wit_import3(ptr0.cast_mut(), len0, ptr1.cast_mut(), len1, ptr2)
+                    };
+                    let l4 = i32::from(*ptr2.add(0).cast::<u8>());
+                    let result8 = match l4 {
                         0 => {
                             let e = ();
                             Ok(e)
                         }
                         1 => {
                             let e = {
-                                let l4 = *ptr2.add(4).cast::<*mut u8>();
-                                let l5 = *ptr2.add(8).cast::<usize>();
-                                let len6 = l5;
-                                let bytes6 = _rt::Vec::from_raw_parts(
-                                    l4.cast(),
-                                    len6,
-                                    len6,
+                                let l5 = *ptr2
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l6 = *ptr2
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len7 = l6;
+                                let bytes7 = _rt::Vec::from_raw_parts(
+                                    l5.cast(),
+                                    len7,
+                                    len7,
                                 );
-                                _rt::string_lift(bytes6)
+                                _rt::string_lift(bytes7)
                             };
                             Err(e)
                         }
                         _ => _rt::invalid_enum_discriminant(),
-                    }
+                    };
+                    result8
                 }
             }
             #[allow(unused_unsafe, clippy::all)]
             pub fn list_files(path: &str) -> Result<_rt::Vec<_rt::String>, _rt::String> {
                 unsafe {
-                    #[repr(align(4))]
-                    struct RetArea([::core::mem::MaybeUninit<u8>; 12]);
-                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 12]);
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
                     let vec0 = path;
                     let ptr0 = vec0.as_ptr().cast::<u8>();
                     let len0 = vec0.len();
                     let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
                     #[link(wasm_import_module = "ntwk:theater/filesystem")]
-                    extern "C" {
+                    unsafe extern "C" {
                         #[link_name = "list-files"]
-                        fn wit_import(_: *mut u8, _: usize, _: *mut u8);
+                        fn wit_import2(_: *mut u8, _: usize, _: *mut u8);
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(_: *mut u8, _: usize, _: *mut u8) {
+                    unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8) {
                         unreachable!()
                     }
-                    wit_import(ptr0.cast_mut(), len0, ptr1);
-                    let l2 = i32::from(*ptr1.add(0).cast::<u8>());
-                    match l2 {
+                    unsafe { wit_import2(ptr0.cast_mut(), len0, ptr1) };
+                    let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+                    let result13 = match l3 {
                         0 => {
                             let e = {
-                                let l3 = *ptr1.add(4).cast::<*mut u8>();
-                                let l4 = *ptr1.add(8).cast::<usize>();
-                                let base8 = l3;
-                                let len8 = l4;
-                                let mut result8 = _rt::Vec::with_capacity(len8);
-                                for i in 0..len8 {
-                                    let base = base8.add(i * 8);
-                                    let e8 = {
-                                        let l5 = *base.add(0).cast::<*mut u8>();
-                                        let l6 = *base.add(4).cast::<usize>();
-                                        let len7 = l6;
-                                        let bytes7 = _rt::Vec::from_raw_parts(
-                                            l5.cast(),
-                                            len7,
-                                            len7,
+                                let l4 = *ptr1
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l5 = *ptr1
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let base9 = l4;
+                                let len9 = l5;
+                                let mut result9 = _rt::Vec::with_capacity(len9);
+                                for i in 0..len9 {
+                                    let base = base9
+                                        .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                                    let e9 = {
+                                        let l6 = *base.add(0).cast::<*mut u8>();
+                                        let l7 = *base
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        let len8 = l7;
+                                        let bytes8 = _rt::Vec::from_raw_parts(
+                                            l6.cast(),
+                                            len8,
+                                            len8,
                                         );
-                                        _rt::string_lift(bytes7)
+                                        _rt::string_lift(bytes8)
                                     };
-                                    result8.push(e8);
+                                    result9.push(e9);
                                 }
-                                _rt::cabi_dealloc(base8, len8 * 8, 4);
-                                result8
+                                _rt::cabi_dealloc(
+                                    base9,
+                                    len9 * (2 * ::core::mem::size_of::<*const u8>()),
+                                    ::core::mem::size_of::<*const u8>(),
+                                );
+                                result9
                             };
                             Ok(e)
                         }
                         1 => {
                             let e = {
-                                let l9 = *ptr1.add(4).cast::<*mut u8>();
-                                let l10 = *ptr1.add(8).cast::<usize>();
-                                let len11 = l10;
-                                let bytes11 = _rt::Vec::from_raw_parts(
-                                    l9.cast(),
-                                    len11,
-                                    len11,
+                                let l10 = *ptr1
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l11 = *ptr1
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len12 = l11;
+                                let bytes12 = _rt::Vec::from_raw_parts(
+                                    l10.cast(),
+                                    len12,
+                                    len12,
                                 );
-                                _rt::string_lift(bytes11)
+                                _rt::string_lift(bytes12)
                             };
                             Err(e)
                         }
                         _ => _rt::invalid_enum_discriminant(),
-                    }
+                    };
+                    result13
                 }
             }
             #[allow(unused_unsafe, clippy::all)]
             pub fn delete_file(path: &str) -> Result<(), _rt::String> {
                 unsafe {
-                    #[repr(align(4))]
-                    struct RetArea([::core::mem::MaybeUninit<u8>; 12]);
-                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 12]);
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
                     let vec0 = path;
                     let ptr0 = vec0.as_ptr().cast::<u8>();
                     let len0 = vec0.len();
                     let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
                     #[link(wasm_import_module = "ntwk:theater/filesystem")]
-                    extern "C" {
+                    unsafe extern "C" {
                         #[link_name = "delete-file"]
-                        fn wit_import(_: *mut u8, _: usize, _: *mut u8);
+                        fn wit_import2(_: *mut u8, _: usize, _: *mut u8);
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(_: *mut u8, _: usize, _: *mut u8) {
+                    unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8) {
                         unreachable!()
                     }
-                    wit_import(ptr0.cast_mut(), len0, ptr1);
-                    let l2 = i32::from(*ptr1.add(0).cast::<u8>());
-                    match l2 {
+                    unsafe { wit_import2(ptr0.cast_mut(), len0, ptr1) };
+                    let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+                    let result7 = match l3 {
                         0 => {
                             let e = ();
                             Ok(e)
                         }
                         1 => {
                             let e = {
-                                let l3 = *ptr1.add(4).cast::<*mut u8>();
-                                let l4 = *ptr1.add(8).cast::<usize>();
-                                let len5 = l4;
-                                let bytes5 = _rt::Vec::from_raw_parts(
-                                    l3.cast(),
-                                    len5,
-                                    len5,
+                                let l4 = *ptr1
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l5 = *ptr1
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len6 = l5;
+                                let bytes6 = _rt::Vec::from_raw_parts(
+                                    l4.cast(),
+                                    len6,
+                                    len6,
                                 );
-                                _rt::string_lift(bytes5)
+                                _rt::string_lift(bytes6)
                             };
                             Err(e)
                         }
                         _ => _rt::invalid_enum_discriminant(),
-                    }
+                    };
+                    result7
                 }
             }
             #[allow(unused_unsafe, clippy::all)]
             pub fn create_dir(path: &str) -> Result<(), _rt::String> {
                 unsafe {
-                    #[repr(align(4))]
-                    struct RetArea([::core::mem::MaybeUninit<u8>; 12]);
-                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 12]);
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
                     let vec0 = path;
                     let ptr0 = vec0.as_ptr().cast::<u8>();
                     let len0 = vec0.len();
                     let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
                     #[link(wasm_import_module = "ntwk:theater/filesystem")]
-                    extern "C" {
+                    unsafe extern "C" {
                         #[link_name = "create-dir"]
-                        fn wit_import(_: *mut u8, _: usize, _: *mut u8);
+                        fn wit_import2(_: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    unsafe { wit_import2(ptr0.cast_mut(), len0, ptr1) };
+                    let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+                    let result7 = match l3 {
+                        0 => {
+                            let e = ();
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l4 = *ptr1
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l5 = *ptr1
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len6 = l5;
+                                let bytes6 = _rt::Vec::from_raw_parts(
+                                    l4.cast(),
+                                    len6,
+                                    len6,
+                                );
+                                _rt::string_lift(bytes6)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result7
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn delete_dir(path: &str) -> Result<(), _rt::String> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let vec0 = path;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "ntwk:theater/filesystem")]
+                    unsafe extern "C" {
+                        #[link_name = "delete-dir"]
+                        fn wit_import2(_: *mut u8, _: usize, _: *mut u8);
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(_: *mut u8, _: usize, _: *mut u8) {
+                    unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8) {
                         unreachable!()
                     }
-                    wit_import(ptr0.cast_mut(), len0, ptr1);
-                    let l2 = i32::from(*ptr1.add(0).cast::<u8>());
-                    match l2 {
+                    unsafe { wit_import2(ptr0.cast_mut(), len0, ptr1) };
+                    let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+                    let result7 = match l3 {
                         0 => {
                             let e = ();
                             Ok(e)
                         }
                         1 => {
                             let e = {
-                                let l3 = *ptr1.add(4).cast::<*mut u8>();
-                                let l4 = *ptr1.add(8).cast::<usize>();
-                                let len5 = l4;
-                                let bytes5 = _rt::Vec::from_raw_parts(
-                                    l3.cast(),
-                                    len5,
-                                    len5,
+                                let l4 = *ptr1
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l5 = *ptr1
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len6 = l5;
+                                let bytes6 = _rt::Vec::from_raw_parts(
+                                    l4.cast(),
+                                    len6,
+                                    len6,
+                                );
+                                _rt::string_lift(bytes6)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result7
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn path_exists(path: &str) -> Result<bool, _rt::String> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let vec0 = path;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "ntwk:theater/filesystem")]
+                    unsafe extern "C" {
+                        #[link_name = "path-exists"]
+                        fn wit_import2(_: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    unsafe { wit_import2(ptr0.cast_mut(), len0, ptr1) };
+                    let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+                    let result8 = match l3 {
+                        0 => {
+                            let e = {
+                                let l4 = i32::from(
+                                    *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                                );
+                                _rt::bool_lift(l4 as u8)
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l5 = *ptr1
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l6 = *ptr1
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len7 = l6;
+                                let bytes7 = _rt::Vec::from_raw_parts(
+                                    l5.cast(),
+                                    len7,
+                                    len7,
+                                );
+                                _rt::string_lift(bytes7)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result8
+                }
+            }
+        }
+        #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+        pub mod store {
+            #[used]
+            #[doc(hidden)]
+            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
+            use super::super::super::_rt;
+            /// Reference to content in the store
+            #[derive(Clone)]
+            pub struct ContentRef {
+                pub hash: _rt::String,
+            }
+            impl ::core::fmt::Debug for ContentRef {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("ContentRef").field("hash", &self.hash).finish()
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Store content and return its ContentRef
+            pub fn store(content: &[u8]) -> Result<ContentRef, _rt::String> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let vec0 = content;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "ntwk:theater/store")]
+                    unsafe extern "C" {
+                        #[link_name = "store"]
+                        fn wit_import2(_: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    unsafe { wit_import2(ptr0.cast_mut(), len0, ptr1) };
+                    let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+                    let result10 = match l3 {
+                        0 => {
+                            let e = {
+                                let l4 = *ptr1
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l5 = *ptr1
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len6 = l5;
+                                let bytes6 = _rt::Vec::from_raw_parts(
+                                    l4.cast(),
+                                    len6,
+                                    len6,
+                                );
+                                ContentRef {
+                                    hash: _rt::string_lift(bytes6),
+                                }
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l7 = *ptr1
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l8 = *ptr1
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len9 = l8;
+                                let bytes9 = _rt::Vec::from_raw_parts(
+                                    l7.cast(),
+                                    len9,
+                                    len9,
+                                );
+                                _rt::string_lift(bytes9)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result10
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Retrieve content by its reference
+            pub fn get(content_ref: &ContentRef) -> Result<_rt::Vec<u8>, _rt::String> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let ContentRef { hash: hash0 } = content_ref;
+                    let vec1 = hash0;
+                    let ptr1 = vec1.as_ptr().cast::<u8>();
+                    let len1 = vec1.len();
+                    let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "ntwk:theater/store")]
+                    unsafe extern "C" {
+                        #[link_name = "get"]
+                        fn wit_import3(_: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import3(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    unsafe { wit_import3(ptr1.cast_mut(), len1, ptr2) };
+                    let l4 = i32::from(*ptr2.add(0).cast::<u8>());
+                    let result11 = match l4 {
+                        0 => {
+                            let e = {
+                                let l5 = *ptr2
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l6 = *ptr2
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len7 = l6;
+                                _rt::Vec::from_raw_parts(l5.cast(), len7, len7)
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l8 = *ptr2
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l9 = *ptr2
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len10 = l9;
+                                let bytes10 = _rt::Vec::from_raw_parts(
+                                    l8.cast(),
+                                    len10,
+                                    len10,
+                                );
+                                _rt::string_lift(bytes10)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result11
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Check if content exists
+            pub fn exists(content_ref: &ContentRef) -> Result<bool, _rt::String> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let ContentRef { hash: hash0 } = content_ref;
+                    let vec1 = hash0;
+                    let ptr1 = vec1.as_ptr().cast::<u8>();
+                    let len1 = vec1.len();
+                    let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "ntwk:theater/store")]
+                    unsafe extern "C" {
+                        #[link_name = "exists"]
+                        fn wit_import3(_: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import3(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    unsafe { wit_import3(ptr1.cast_mut(), len1, ptr2) };
+                    let l4 = i32::from(*ptr2.add(0).cast::<u8>());
+                    let result9 = match l4 {
+                        0 => {
+                            let e = {
+                                let l5 = i32::from(
+                                    *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                                );
+                                _rt::bool_lift(l5 as u8)
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l6 = *ptr2
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l7 = *ptr2
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len8 = l7;
+                                let bytes8 = _rt::Vec::from_raw_parts(
+                                    l6.cast(),
+                                    len8,
+                                    len8,
+                                );
+                                _rt::string_lift(bytes8)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result9
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Attach a label to content
+            pub fn label(
+                label: &str,
+                content_ref: &ContentRef,
+            ) -> Result<(), _rt::String> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let vec0 = label;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ContentRef { hash: hash1 } = content_ref;
+                    let vec2 = hash1;
+                    let ptr2 = vec2.as_ptr().cast::<u8>();
+                    let len2 = vec2.len();
+                    let ptr3 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "ntwk:theater/store")]
+                    unsafe extern "C" {
+                        #[link_name = "label"]
+                        fn wit_import4(
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                        );
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import4(
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                    ) {
+                        unreachable!()
+                    }
+                    unsafe {
+                        wit_import4(ptr0.cast_mut(), len0, ptr2.cast_mut(), len2, ptr3)
+                    };
+                    let l5 = i32::from(*ptr3.add(0).cast::<u8>());
+                    let result9 = match l5 {
+                        0 => {
+                            let e = ();
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l6 = *ptr3
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l7 = *ptr3
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len8 = l7;
+                                let bytes8 = _rt::Vec::from_raw_parts(
+                                    l6.cast(),
+                                    len8,
+                                    len8,
+                                );
+                                _rt::string_lift(bytes8)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result9
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Get content references by label
+            pub fn get_by_label(
+                label: &str,
+            ) -> Result<_rt::Vec<ContentRef>, _rt::String> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let vec0 = label;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "ntwk:theater/store")]
+                    unsafe extern "C" {
+                        #[link_name = "get-by-label"]
+                        fn wit_import2(_: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    unsafe { wit_import2(ptr0.cast_mut(), len0, ptr1) };
+                    let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+                    let result13 = match l3 {
+                        0 => {
+                            let e = {
+                                let l4 = *ptr1
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l5 = *ptr1
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let base9 = l4;
+                                let len9 = l5;
+                                let mut result9 = _rt::Vec::with_capacity(len9);
+                                for i in 0..len9 {
+                                    let base = base9
+                                        .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                                    let e9 = {
+                                        let l6 = *base.add(0).cast::<*mut u8>();
+                                        let l7 = *base
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        let len8 = l7;
+                                        let bytes8 = _rt::Vec::from_raw_parts(
+                                            l6.cast(),
+                                            len8,
+                                            len8,
+                                        );
+                                        ContentRef {
+                                            hash: _rt::string_lift(bytes8),
+                                        }
+                                    };
+                                    result9.push(e9);
+                                }
+                                _rt::cabi_dealloc(
+                                    base9,
+                                    len9 * (2 * ::core::mem::size_of::<*const u8>()),
+                                    ::core::mem::size_of::<*const u8>(),
+                                );
+                                result9
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l10 = *ptr1
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l11 = *ptr1
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len12 = l11;
+                                let bytes12 = _rt::Vec::from_raw_parts(
+                                    l10.cast(),
+                                    len12,
+                                    len12,
+                                );
+                                _rt::string_lift(bytes12)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result13
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Remove a label
+            pub fn remove_label(label: &str) -> Result<(), _rt::String> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let vec0 = label;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "ntwk:theater/store")]
+                    unsafe extern "C" {
+                        #[link_name = "remove-label"]
+                        fn wit_import2(_: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    unsafe { wit_import2(ptr0.cast_mut(), len0, ptr1) };
+                    let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+                    let result7 = match l3 {
+                        0 => {
+                            let e = ();
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l4 = *ptr1
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l5 = *ptr1
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len6 = l5;
+                                let bytes6 = _rt::Vec::from_raw_parts(
+                                    l4.cast(),
+                                    len6,
+                                    len6,
+                                );
+                                _rt::string_lift(bytes6)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result7
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Remove a specific content reference from a label
+            pub fn remove_from_label(
+                label: &str,
+                content_ref: &ContentRef,
+            ) -> Result<(), _rt::String> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let vec0 = label;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ContentRef { hash: hash1 } = content_ref;
+                    let vec2 = hash1;
+                    let ptr2 = vec2.as_ptr().cast::<u8>();
+                    let len2 = vec2.len();
+                    let ptr3 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "ntwk:theater/store")]
+                    unsafe extern "C" {
+                        #[link_name = "remove-from-label"]
+                        fn wit_import4(
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                        );
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import4(
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                    ) {
+                        unreachable!()
+                    }
+                    unsafe {
+                        wit_import4(ptr0.cast_mut(), len0, ptr2.cast_mut(), len2, ptr3)
+                    };
+                    let l5 = i32::from(*ptr3.add(0).cast::<u8>());
+                    let result9 = match l5 {
+                        0 => {
+                            let e = ();
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l6 = *ptr3
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l7 = *ptr3
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len8 = l7;
+                                let bytes8 = _rt::Vec::from_raw_parts(
+                                    l6.cast(),
+                                    len8,
+                                    len8,
+                                );
+                                _rt::string_lift(bytes8)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result9
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Store content and immediately label it
+            pub fn put_at_label(
+                label: &str,
+                content: &[u8],
+            ) -> Result<ContentRef, _rt::String> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let vec0 = label;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let vec1 = content;
+                    let ptr1 = vec1.as_ptr().cast::<u8>();
+                    let len1 = vec1.len();
+                    let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "ntwk:theater/store")]
+                    unsafe extern "C" {
+                        #[link_name = "put-at-label"]
+                        fn wit_import3(
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                        );
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import3(
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                    ) {
+                        unreachable!()
+                    }
+                    unsafe {
+                        wit_import3(ptr0.cast_mut(), len0, ptr1.cast_mut(), len1, ptr2)
+                    };
+                    let l4 = i32::from(*ptr2.add(0).cast::<u8>());
+                    let result11 = match l4 {
+                        0 => {
+                            let e = {
+                                let l5 = *ptr2
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l6 = *ptr2
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len7 = l6;
+                                let bytes7 = _rt::Vec::from_raw_parts(
+                                    l5.cast(),
+                                    len7,
+                                    len7,
+                                );
+                                ContentRef {
+                                    hash: _rt::string_lift(bytes7),
+                                }
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l8 = *ptr2
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l9 = *ptr2
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len10 = l9;
+                                let bytes10 = _rt::Vec::from_raw_parts(
+                                    l8.cast(),
+                                    len10,
+                                    len10,
+                                );
+                                _rt::string_lift(bytes10)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result11
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Put content at a label, replacing any existing content
+            pub fn replace_content_at_label(
+                label: &str,
+                content: &[u8],
+            ) -> Result<ContentRef, _rt::String> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let vec0 = label;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let vec1 = content;
+                    let ptr1 = vec1.as_ptr().cast::<u8>();
+                    let len1 = vec1.len();
+                    let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "ntwk:theater/store")]
+                    unsafe extern "C" {
+                        #[link_name = "replace-content-at-label"]
+                        fn wit_import3(
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                        );
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import3(
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                    ) {
+                        unreachable!()
+                    }
+                    unsafe {
+                        wit_import3(ptr0.cast_mut(), len0, ptr1.cast_mut(), len1, ptr2)
+                    };
+                    let l4 = i32::from(*ptr2.add(0).cast::<u8>());
+                    let result11 = match l4 {
+                        0 => {
+                            let e = {
+                                let l5 = *ptr2
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l6 = *ptr2
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len7 = l6;
+                                let bytes7 = _rt::Vec::from_raw_parts(
+                                    l5.cast(),
+                                    len7,
+                                    len7,
+                                );
+                                ContentRef {
+                                    hash: _rt::string_lift(bytes7),
+                                }
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l8 = *ptr2
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l9 = *ptr2
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len10 = l9;
+                                let bytes10 = _rt::Vec::from_raw_parts(
+                                    l8.cast(),
+                                    len10,
+                                    len10,
+                                );
+                                _rt::string_lift(bytes10)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result11
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Replace content at a label with a specific content reference
+            pub fn replace_at_label(
+                label: &str,
+                content_ref: &ContentRef,
+            ) -> Result<(), _rt::String> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let vec0 = label;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ContentRef { hash: hash1 } = content_ref;
+                    let vec2 = hash1;
+                    let ptr2 = vec2.as_ptr().cast::<u8>();
+                    let len2 = vec2.len();
+                    let ptr3 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "ntwk:theater/store")]
+                    unsafe extern "C" {
+                        #[link_name = "replace-at-label"]
+                        fn wit_import4(
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                        );
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import4(
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                    ) {
+                        unreachable!()
+                    }
+                    unsafe {
+                        wit_import4(ptr0.cast_mut(), len0, ptr2.cast_mut(), len2, ptr3)
+                    };
+                    let l5 = i32::from(*ptr3.add(0).cast::<u8>());
+                    let result9 = match l5 {
+                        0 => {
+                            let e = ();
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l6 = *ptr3
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l7 = *ptr3
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len8 = l7;
+                                let bytes8 = _rt::Vec::from_raw_parts(
+                                    l6.cast(),
+                                    len8,
+                                    len8,
+                                );
+                                _rt::string_lift(bytes8)
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result9
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// List all labels
+            pub fn list_labels() -> Result<_rt::Vec<_rt::String>, _rt::String> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "ntwk:theater/store")]
+                    unsafe extern "C" {
+                        #[link_name = "list-labels"]
+                        fn wit_import1(_: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import1(_: *mut u8) {
+                        unreachable!()
+                    }
+                    unsafe { wit_import1(ptr0) };
+                    let l2 = i32::from(*ptr0.add(0).cast::<u8>());
+                    let result12 = match l2 {
+                        0 => {
+                            let e = {
+                                let l3 = *ptr0
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l4 = *ptr0
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let base8 = l3;
+                                let len8 = l4;
+                                let mut result8 = _rt::Vec::with_capacity(len8);
+                                for i in 0..len8 {
+                                    let base = base8
+                                        .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                                    let e8 = {
+                                        let l5 = *base.add(0).cast::<*mut u8>();
+                                        let l6 = *base
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        let len7 = l6;
+                                        let bytes7 = _rt::Vec::from_raw_parts(
+                                            l5.cast(),
+                                            len7,
+                                            len7,
+                                        );
+                                        _rt::string_lift(bytes7)
+                                    };
+                                    result8.push(e8);
+                                }
+                                _rt::cabi_dealloc(
+                                    base8,
+                                    len8 * (2 * ::core::mem::size_of::<*const u8>()),
+                                    ::core::mem::size_of::<*const u8>(),
+                                );
+                                result8
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l9 = *ptr0
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l10 = *ptr0
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len11 = l10;
+                                let bytes11 = _rt::Vec::from_raw_parts(
+                                    l9.cast(),
+                                    len11,
+                                    len11,
                                 );
-                                _rt::string_lift(bytes5)
+                                _rt::string_lift(bytes11)
                             };
                             Err(e)
                         }
                         _ => _rt::invalid_enum_discriminant(),
-                    }
+                    };
+                    result12
                 }
             }
             #[allow(unused_unsafe, clippy::all)]
-            pub fn delete_dir(path: &str) -> Result<(), _rt::String> {
+            /// List all content references
+            pub fn list_all_content() -> Result<_rt::Vec<ContentRef>, _rt::String> {
                 unsafe {
-                    #[repr(align(4))]
-                    struct RetArea([::core::mem::MaybeUninit<u8>; 12]);
-                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 12]);
-                    let vec0 = path;
-                    let ptr0 = vec0.as_ptr().cast::<u8>();
-                    let len0 = vec0.len();
-                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "ntwk:theater/filesystem")]
-                    extern "C" {
-                        #[link_name = "delete-dir"]
-                        fn wit_import(_: *mut u8, _: usize, _: *mut u8);
+                    #[link(wasm_import_module = "ntwk:theater/store")]
+                    unsafe extern "C" {
+                        #[link_name = "list-all-content"]
+                        fn wit_import1(_: *mut u8);
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(_: *mut u8, _: usize, _: *mut u8) {
+                    unsafe extern "C" fn wit_import1(_: *mut u8) {
                         unreachable!()
                     }
-                    wit_import(ptr0.cast_mut(), len0, ptr1);
-                    let l2 = i32::from(*ptr1.add(0).cast::<u8>());
-                    match l2 {
+                    unsafe { wit_import1(ptr0) };
+                    let l2 = i32::from(*ptr0.add(0).cast::<u8>());
+                    let result12 = match l2 {
                         0 => {
-                            let e = ();
+                            let e = {
+                                let l3 = *ptr0
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l4 = *ptr0
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let base8 = l3;
+                                let len8 = l4;
+                                let mut result8 = _rt::Vec::with_capacity(len8);
+                                for i in 0..len8 {
+                                    let base = base8
+                                        .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                                    let e8 = {
+                                        let l5 = *base.add(0).cast::<*mut u8>();
+                                        let l6 = *base
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        let len7 = l6;
+                                        let bytes7 = _rt::Vec::from_raw_parts(
+                                            l5.cast(),
+                                            len7,
+                                            len7,
+                                        );
+                                        ContentRef {
+                                            hash: _rt::string_lift(bytes7),
+                                        }
+                                    };
+                                    result8.push(e8);
+                                }
+                                _rt::cabi_dealloc(
+                                    base8,
+                                    len8 * (2 * ::core::mem::size_of::<*const u8>()),
+                                    ::core::mem::size_of::<*const u8>(),
+                                );
+                                result8
+                            };
                             Ok(e)
                         }
                         1 => {
                             let e = {
-                                let l3 = *ptr1.add(4).cast::<*mut u8>();
-                                let l4 = *ptr1.add(8).cast::<usize>();
-                                let len5 = l4;
-                                let bytes5 = _rt::Vec::from_raw_parts(
-                                    l3.cast(),
-                                    len5,
-                                    len5,
+                                let l9 = *ptr0
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l10 = *ptr0
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len11 = l10;
+                                let bytes11 = _rt::Vec::from_raw_parts(
+                                    l9.cast(),
+                                    len11,
+                                    len11,
                                 );
-                                _rt::string_lift(bytes5)
+                                _rt::string_lift(bytes11)
                             };
                             Err(e)
                         }
                         _ => _rt::invalid_enum_discriminant(),
-                    }
+                    };
+                    result12
                 }
             }
             #[allow(unused_unsafe, clippy::all)]
-            pub fn path_exists(path: &str) -> Result<bool, _rt::String> {
+            /// Calculate total size of all content
+            pub fn calculate_total_size() -> Result<u64, _rt::String> {
                 unsafe {
-                    #[repr(align(4))]
-                    struct RetArea([::core::mem::MaybeUninit<u8>; 12]);
-                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 12]);
-                    let vec0 = path;
-                    let ptr0 = vec0.as_ptr().cast::<u8>();
-                    let len0 = vec0.len();
-                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[repr(align(8))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 8 + 2 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 8
+                            + 2 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "ntwk:theater/filesystem")]
-                    extern "C" {
-                        #[link_name = "path-exists"]
-                        fn wit_import(_: *mut u8, _: usize, _: *mut u8);
+                    #[link(wasm_import_module = "ntwk:theater/store")]
+                    unsafe extern "C" {
+                        #[link_name = "calculate-total-size"]
+                        fn wit_import1(_: *mut u8);
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(_: *mut u8, _: usize, _: *mut u8) {
+                    unsafe extern "C" fn wit_import1(_: *mut u8) {
                         unreachable!()
                     }
-                    wit_import(ptr0.cast_mut(), len0, ptr1);
-                    let l2 = i32::from(*ptr1.add(0).cast::<u8>());
-                    match l2 {
+                    unsafe { wit_import1(ptr0) };
+                    let l2 = i32::from(*ptr0.add(0).cast::<u8>());
+                    let result7 = match l2 {
                         0 => {
                             let e = {
-                                let l3 = i32::from(*ptr1.add(4).cast::<u8>());
-                                _rt::bool_lift(l3 as u8)
+                                let l3 = *ptr0.add(8).cast::<i64>();
+                                l3 as u64
                             };
                             Ok(e)
                         }
                         1 => {
                             let e = {
-                                let l4 = *ptr1.add(4).cast::<*mut u8>();
-                                let l5 = *ptr1.add(8).cast::<usize>();
+                                let l4 = *ptr0.add(8).cast::<*mut u8>();
+                                let l5 = *ptr0
+                                    .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
                                 let len6 = l5;
                                 let bytes6 = _rt::Vec::from_raw_parts(
                                     l4.cast(),
@@ -516,11 +1706,12 @@ pub mod ntwk {
                             Err(e)
                         }
                         _ => _rt::invalid_enum_discriminant(),
-                    }
+                    };
+                    result7
                 }
             }
         }
-        #[allow(dead_code, clippy::all)]
+        #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
         pub mod message_server_host {
             #[used]
             #[doc(hidden)]
@@ -530,11 +1721,19 @@ pub mod ntwk {
             pub type ActorId = super::super::super::ntwk::theater::types::ActorId;
             #[allow(unused_unsafe, clippy::all)]
             /// send and forget message
-            pub fn send(actor_id: &ActorId, msg: &Json) -> Result<(), _rt::String> {
+            pub fn send(actor_id: &str, msg: &[u8]) -> Result<(), _rt::String> {
                 unsafe {
-                    #[repr(align(4))]
-                    struct RetArea([::core::mem::MaybeUninit<u8>; 12]);
-                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 12]);
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
                     let vec0 = actor_id;
                     let ptr0 = vec0.as_ptr().cast::<u8>();
                     let len0 = vec0.len();
@@ -544,9 +1743,9 @@ pub mod ntwk {
                     let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
                     #[link(wasm_import_module = "ntwk:theater/message-server-host")]
-                    extern "C" {
+                    unsafe extern "C" {
                         #[link_name = "send"]
-                        fn wit_import(
+                        fn wit_import3(
                             _: *mut u8,
                             _: usize,
                             _: *mut u8,
@@ -555,7 +1754,7 @@ pub mod ntwk {
                         );
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(
+                    unsafe extern "C" fn wit_import3(
                         _: *mut u8,
                         _: usize,
                         _: *mut u8,
@@ -564,38 +1763,53 @@ pub mod ntwk {
                     ) {
                         unreachable!()
                     }
-                    wit_import(ptr0.cast_mut(), len0, ptr1.cast_mut(), len1, ptr2);
-                    let l3 = i32::from(*ptr2.add(0).cast::<u8>());
-                    match l3 {
+                    unsafe {
+                        wit_import3(ptr0.cast_mut(), len0, ptr1.cast_mut(), len1, ptr2)
+                    };
+                    let l4 = i32::from(*ptr2.add(0).cast::<u8>());
+                    let result8 = match l4 {
                         0 => {
                             let e = ();
                             Ok(e)
                         }
                         1 => {
                             let e = {
-                                let l4 = *ptr2.add(4).cast::<*mut u8>();
-                                let l5 = *ptr2.add(8).cast::<usize>();
-                                let len6 = l5;
-                                let bytes6 = _rt::Vec::from_raw_parts(
-                                    l4.cast(),
-                                    len6,
-                                    len6,
+                                let l5 = *ptr2
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l6 = *ptr2
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len7 = l6;
+                                let bytes7 = _rt::Vec::from_raw_parts(
+                                    l5.cast(),
+                                    len7,
+                                    len7,
                                 );
-                                _rt::string_lift(bytes6)
+                                _rt::string_lift(bytes7)
                             };
                             Err(e)
                         }
                         _ => _rt::invalid_enum_discriminant(),
-                    }
+                    };
+                    result8
                 }
             }
             #[allow(unused_unsafe, clippy::all)]
             /// send message and wait for response
-            pub fn request(actor_id: &ActorId, msg: &Json) -> Result<Json, _rt::String> {
+            pub fn request(actor_id: &str, msg: &[u8]) -> Result<Json, _rt::String> {
                 unsafe {
-                    #[repr(align(4))]
-                    struct RetArea([::core::mem::MaybeUninit<u8>; 12]);
-                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 12]);
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
                     let vec0 = actor_id;
                     let ptr0 = vec0.as_ptr().cast::<u8>();
                     let len0 = vec0.len();
@@ -605,9 +1819,9 @@ pub mod ntwk {
                     let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
                     #[link(wasm_import_module = "ntwk:theater/message-server-host")]
-                    extern "C" {
+                    unsafe extern "C" {
                         #[link_name = "request"]
-                        fn wit_import(
+                        fn wit_import3(
                             _: *mut u8,
                             _: usize,
                             _: *mut u8,
@@ -616,7 +1830,7 @@ pub mod ntwk {
                         );
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(
+                    unsafe extern "C" fn wit_import3(
                         _: *mut u8,
                         _: usize,
                         _: *mut u8,
@@ -625,38 +1839,49 @@ pub mod ntwk {
                     ) {
                         unreachable!()
                     }
-                    wit_import(ptr0.cast_mut(), len0, ptr1.cast_mut(), len1, ptr2);
-                    let l3 = i32::from(*ptr2.add(0).cast::<u8>());
-                    match l3 {
+                    unsafe {
+                        wit_import3(ptr0.cast_mut(), len0, ptr1.cast_mut(), len1, ptr2)
+                    };
+                    let l4 = i32::from(*ptr2.add(0).cast::<u8>());
+                    let result11 = match l4 {
                         0 => {
                             let e = {
-                                let l4 = *ptr2.add(4).cast::<*mut u8>();
-                                let l5 = *ptr2.add(8).cast::<usize>();
-                                let len6 = l5;
-                                _rt::Vec::from_raw_parts(l4.cast(), len6, len6)
+                                let l5 = *ptr2
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l6 = *ptr2
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len7 = l6;
+                                _rt::Vec::from_raw_parts(l5.cast(), len7, len7)
                             };
                             Ok(e)
                         }
                         1 => {
                             let e = {
-                                let l7 = *ptr2.add(4).cast::<*mut u8>();
-                                let l8 = *ptr2.add(8).cast::<usize>();
-                                let len9 = l8;
-                                let bytes9 = _rt::Vec::from_raw_parts(
-                                    l7.cast(),
-                                    len9,
-                                    len9,
+                                let l8 = *ptr2
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l9 = *ptr2
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len10 = l9;
+                                let bytes10 = _rt::Vec::from_raw_parts(
+                                    l8.cast(),
+                                    len10,
+                                    len10,
                                 );
-                                _rt::string_lift(bytes9)
+                                _rt::string_lift(bytes10)
                             };
                             Err(e)
                         }
                         _ => _rt::invalid_enum_discriminant(),
-                    }
+                    };
+                    result11
                 }
             }
         }
-        #[allow(dead_code, clippy::all)]
+        #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
         pub mod http_types {
             #[used]
             #[doc(hidden)]
@@ -702,7 +1927,7 @@ pub mod ntwk {
                 }
             }
         }
-        #[allow(dead_code, clippy::all)]
+        #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
         pub mod http_client {
             #[used]
             #[doc(hidden)]
@@ -713,9 +1938,17 @@ pub mod ntwk {
             #[allow(unused_unsafe, clippy::all)]
             pub fn send_http(req: &HttpRequest) -> Result<HttpResponse, _rt::String> {
                 unsafe {
-                    #[repr(align(4))]
-                    struct RetArea([::core::mem::MaybeUninit<u8>; 28]);
-                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 28]);
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 7 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 7
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
                     let super::super::super::ntwk::theater::http_types::HttpRequest {
                         method: method0,
                         uri: uri0,
@@ -731,8 +1964,8 @@ pub mod ntwk {
                     let vec6 = headers0;
                     let len6 = vec6.len();
                     let layout6 = _rt::alloc::Layout::from_size_align_unchecked(
-                        vec6.len() * 16,
-                        4,
+                        vec6.len() * (4 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
                     );
                     let result6 = if layout6.size() != 0 {
                         let ptr = _rt::alloc::alloc(layout6).cast::<u8>();
@@ -744,19 +1977,26 @@ pub mod ntwk {
                         ::core::ptr::null_mut()
                     };
                     for (i, e) in vec6.into_iter().enumerate() {
-                        let base = result6.add(i * 16);
+                        let base = result6
+                            .add(i * (4 * ::core::mem::size_of::<*const u8>()));
                         {
                             let (t3_0, t3_1) = e;
                             let vec4 = t3_0;
                             let ptr4 = vec4.as_ptr().cast::<u8>();
                             let len4 = vec4.len();
-                            *base.add(4).cast::<usize>() = len4;
+                            *base
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len4;
                             *base.add(0).cast::<*mut u8>() = ptr4.cast_mut();
                             let vec5 = t3_1;
                             let ptr5 = vec5.as_ptr().cast::<u8>();
                             let len5 = vec5.len();
-                            *base.add(12).cast::<usize>() = len5;
-                            *base.add(8).cast::<*mut u8>() = ptr5.cast_mut();
+                            *base
+                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len5;
+                            *base
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr5.cast_mut();
                         }
                     }
                     let (result8_0, result8_1, result8_2) = match body0 {
@@ -771,9 +2011,9 @@ pub mod ntwk {
                     let ptr9 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
                     #[link(wasm_import_module = "ntwk:theater/http-client")]
-                    extern "C" {
+                    unsafe extern "C" {
                         #[link_name = "send-http"]
-                        fn wit_import(
+                        fn wit_import10(
                             _: *mut u8,
                             _: usize,
                             _: *mut u8,
@@ -787,7 +2027,7 @@ pub mod ntwk {
                         );
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(
+                    unsafe extern "C" fn wit_import10(
                         _: *mut u8,
                         _: usize,
                         _: *mut u8,
@@ -801,67 +2041,91 @@ pub mod ntwk {
                     ) {
                         unreachable!()
                     }
-                    wit_import(
-                        ptr1.cast_mut(),
-                        len1,
-                        ptr2.cast_mut(),
-                        len2,
-                        result6,
-                        len6,
-                        result8_0,
-                        result8_1,
-                        result8_2,
-                        ptr9,
-                    );
-                    let l10 = i32::from(*ptr9.add(0).cast::<u8>());
-                    if layout6.size() != 0 {
-                        _rt::alloc::dealloc(result6.cast(), layout6);
-                    }
-                    match l10 {
+                    unsafe {
+                        wit_import10(
+                            ptr1.cast_mut(),
+                            len1,
+                            ptr2.cast_mut(),
+                            len2,
+                            result6,
+                            len6,
+                            result8_0,
+                            result8_1,
+                            result8_2,
+                            ptr9,
+                        )
+                    };
+                    let l11 = i32::from(*ptr9.add(0).cast::<u8>());
+                    let result29 = match l11 {
                         0 => {
                             let e = {
-                                let l11 = i32::from(*ptr9.add(4).cast::<u16>());
-                                let l12 = *ptr9.add(8).cast::<*mut u8>();
-                                let l13 = *ptr9.add(12).cast::<usize>();
-                                let base20 = l12;
-                                let len20 = l13;
-                                let mut result20 = _rt::Vec::with_capacity(len20);
-                                for i in 0..len20 {
-                                    let base = base20.add(i * 16);
-                                    let e20 = {
-                                        let l14 = *base.add(0).cast::<*mut u8>();
-                                        let l15 = *base.add(4).cast::<usize>();
-                                        let len16 = l15;
-                                        let bytes16 = _rt::Vec::from_raw_parts(
-                                            l14.cast(),
-                                            len16,
-                                            len16,
+                                let l12 = i32::from(
+                                    *ptr9.add(::core::mem::size_of::<*const u8>()).cast::<u16>(),
+                                );
+                                let l13 = *ptr9
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l14 = *ptr9
+                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let base21 = l13;
+                                let len21 = l14;
+                                let mut result21 = _rt::Vec::with_capacity(len21);
+                                for i in 0..len21 {
+                                    let base = base21
+                                        .add(i * (4 * ::core::mem::size_of::<*const u8>()));
+                                    let e21 = {
+                                        let l15 = *base.add(0).cast::<*mut u8>();
+                                        let l16 = *base
+                                            .add(::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        let len17 = l16;
+                                        let bytes17 = _rt::Vec::from_raw_parts(
+                                            l15.cast(),
+                                            len17,
+                                            len17,
                                         );
-                                        let l17 = *base.add(8).cast::<*mut u8>();
-                                        let l18 = *base.add(12).cast::<usize>();
-                                        let len19 = l18;
-                                        let bytes19 = _rt::Vec::from_raw_parts(
-                                            l17.cast(),
-                                            len19,
-                                            len19,
+                                        let l18 = *base
+                                            .add(2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>();
+                                        let l19 = *base
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        let len20 = l19;
+                                        let bytes20 = _rt::Vec::from_raw_parts(
+                                            l18.cast(),
+                                            len20,
+                                            len20,
                                         );
-                                        (_rt::string_lift(bytes16), _rt::string_lift(bytes19))
+                                        (_rt::string_lift(bytes17), _rt::string_lift(bytes20))
                                     };
-                                    result20.push(e20);
+                                    result21.push(e21);
                                 }
-                                _rt::cabi_dealloc(base20, len20 * 16, 4);
-                                let l21 = i32::from(*ptr9.add(16).cast::<u8>());
+                                _rt::cabi_dealloc(
+                                    base21,
+                                    len21 * (4 * ::core::mem::size_of::<*const u8>()),
+                                    ::core::mem::size_of::<*const u8>(),
+                                );
+                                let l22 = i32::from(
+                                    *ptr9
+                                        .add(4 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>(),
+                                );
                                 super::super::super::ntwk::theater::http_types::HttpResponse {
-                                    status: l11 as u16,
-                                    headers: result20,
-                                    body: match l21 {
+                                    status: l12 as u16,
+                                    headers: result21,
+                                    body: match l22 {
                                         0 => None,
                                         1 => {
                                             let e = {
-                                                let l22 = *ptr9.add(20).cast::<*mut u8>();
-                                                let l23 = *ptr9.add(24).cast::<usize>();
-                                                let len24 = l23;
-                                                _rt::Vec::from_raw_parts(l22.cast(), len24, len24)
+                                                let l23 = *ptr9
+                                                    .add(5 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l24 = *ptr9
+                                                    .add(6 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len25 = l24;
+                                                _rt::Vec::from_raw_parts(l23.cast(), len25, len25)
                                             };
                                             Some(e)
                                         }
@@ -873,20 +2137,28 @@ pub mod ntwk {
                         }
                         1 => {
                             let e = {
-                                let l25 = *ptr9.add(4).cast::<*mut u8>();
-                                let l26 = *ptr9.add(8).cast::<usize>();
-                                let len27 = l26;
-                                let bytes27 = _rt::Vec::from_raw_parts(
-                                    l25.cast(),
-                                    len27,
-                                    len27,
+                                let l26 = *ptr9
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l27 = *ptr9
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len28 = l27;
+                                let bytes28 = _rt::Vec::from_raw_parts(
+                                    l26.cast(),
+                                    len28,
+                                    len28,
                                 );
-                                _rt::string_lift(bytes27)
+                                _rt::string_lift(bytes28)
                             };
                             Err(e)
                         }
                         _ => _rt::invalid_enum_discriminant(),
+                    };
+                    if layout6.size() != 0 {
+                        _rt::alloc::dealloc(result6.cast(), layout6);
                     }
+                    result29
                 }
             }
         }
@@ -897,7 +2169,7 @@ pub mod ntwk {
 pub mod exports {
     pub mod ntwk {
         pub mod theater {
-            #[allow(dead_code, clippy::all)]
+            #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
             pub mod message_server_client {
                 #[used]
                 #[doc(hidden)]
@@ -929,23 +2201,31 @@ pub mod exports {
                         },
                         (_rt::Vec::from_raw_parts(arg3.cast(), len1, len1),),
                     );
-                    let ptr3 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    let ptr3 = (&raw mut _RET_AREA.0).cast::<u8>();
                     match result2 {
                         Ok(e) => {
                             *ptr3.add(0).cast::<u8>() = (0i32) as u8;
                             let (t4_0,) = e;
                             match t4_0 {
                                 Some(e) => {
-                                    *ptr3.add(4).cast::<u8>() = (1i32) as u8;
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
                                     let vec5 = (e).into_boxed_slice();
                                     let ptr5 = vec5.as_ptr().cast::<u8>();
                                     let len5 = vec5.len();
                                     ::core::mem::forget(vec5);
-                                    *ptr3.add(12).cast::<usize>() = len5;
-                                    *ptr3.add(8).cast::<*mut u8>() = ptr5.cast_mut();
+                                    *ptr3
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len5;
+                                    *ptr3
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr5.cast_mut();
                                 }
                                 None => {
-                                    *ptr3.add(4).cast::<u8>() = (0i32) as u8;
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
                                 }
                             };
                         }
@@ -955,8 +2235,12 @@ pub mod exports {
                             let ptr6 = vec6.as_ptr().cast::<u8>();
                             let len6 = vec6.len();
                             ::core::mem::forget(vec6);
-                            *ptr3.add(8).cast::<usize>() = len6;
-                            *ptr3.add(4).cast::<*mut u8>() = ptr6.cast_mut();
+                            *ptr3
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len6;
+                            *ptr3
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr6.cast_mut();
                         }
                     };
                     ptr3
@@ -967,12 +2251,18 @@ pub mod exports {
                     let l0 = i32::from(*arg0.add(0).cast::<u8>());
                     match l0 {
                         0 => {
-                            let l1 = i32::from(*arg0.add(4).cast::<u8>());
+                            let l1 = i32::from(
+                                *arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                            );
                             match l1 {
                                 0 => {}
                                 _ => {
-                                    let l2 = *arg0.add(8).cast::<*mut u8>();
-                                    let l3 = *arg0.add(12).cast::<usize>();
+                                    let l2 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l3 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
                                     let base4 = l2;
                                     let len4 = l3;
                                     _rt::cabi_dealloc(base4, len4 * 1, 1);
@@ -980,8 +2270,12 @@ pub mod exports {
                             }
                         }
                         _ => {
-                            let l5 = *arg0.add(4).cast::<*mut u8>();
-                            let l6 = *arg0.add(8).cast::<usize>();
+                            let l5 = *arg0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l6 = *arg0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
                             _rt::cabi_dealloc(l5, l6, 1);
                         }
                     }
@@ -1011,23 +2305,31 @@ pub mod exports {
                         },
                         (_rt::Vec::from_raw_parts(arg3.cast(), len1, len1),),
                     );
-                    let ptr3 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    let ptr3 = (&raw mut _RET_AREA.0).cast::<u8>();
                     match result2 {
                         Ok(e) => {
                             *ptr3.add(0).cast::<u8>() = (0i32) as u8;
                             let (t4_0, t4_1) = e;
                             match t4_0 {
                                 Some(e) => {
-                                    *ptr3.add(4).cast::<u8>() = (1i32) as u8;
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
                                     let vec5 = (e).into_boxed_slice();
                                     let ptr5 = vec5.as_ptr().cast::<u8>();
                                     let len5 = vec5.len();
                                     ::core::mem::forget(vec5);
-                                    *ptr3.add(12).cast::<usize>() = len5;
-                                    *ptr3.add(8).cast::<*mut u8>() = ptr5.cast_mut();
+                                    *ptr3
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len5;
+                                    *ptr3
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr5.cast_mut();
                                 }
                                 None => {
-                                    *ptr3.add(4).cast::<u8>() = (0i32) as u8;
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
                                 }
                             };
                             let (t6_0,) = t4_1;
@@ -1035,8 +2337,12 @@ pub mod exports {
                             let ptr7 = vec7.as_ptr().cast::<u8>();
                             let len7 = vec7.len();
                             ::core::mem::forget(vec7);
-                            *ptr3.add(20).cast::<usize>() = len7;
-                            *ptr3.add(16).cast::<*mut u8>() = ptr7.cast_mut();
+                            *ptr3
+                                .add(5 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len7;
+                            *ptr3
+                                .add(4 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr7.cast_mut();
                         }
                         Err(e) => {
                             *ptr3.add(0).cast::<u8>() = (1i32) as u8;
@@ -1044,8 +2350,12 @@ pub mod exports {
                             let ptr8 = vec8.as_ptr().cast::<u8>();
                             let len8 = vec8.len();
                             ::core::mem::forget(vec8);
-                            *ptr3.add(8).cast::<usize>() = len8;
-                            *ptr3.add(4).cast::<*mut u8>() = ptr8.cast_mut();
+                            *ptr3
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len8;
+                            *ptr3
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr8.cast_mut();
                         }
                     };
                     ptr3
@@ -1056,26 +2366,40 @@ pub mod exports {
                     let l0 = i32::from(*arg0.add(0).cast::<u8>());
                     match l0 {
                         0 => {
-                            let l1 = i32::from(*arg0.add(4).cast::<u8>());
+                            let l1 = i32::from(
+                                *arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                            );
                             match l1 {
                                 0 => {}
                                 _ => {
-                                    let l2 = *arg0.add(8).cast::<*mut u8>();
-                                    let l3 = *arg0.add(12).cast::<usize>();
+                                    let l2 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l3 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
                                     let base4 = l2;
                                     let len4 = l3;
                                     _rt::cabi_dealloc(base4, len4 * 1, 1);
                                 }
                             }
-                            let l5 = *arg0.add(16).cast::<*mut u8>();
-                            let l6 = *arg0.add(20).cast::<usize>();
+                            let l5 = *arg0
+                                .add(4 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l6 = *arg0
+                                .add(5 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
                             let base7 = l5;
                             let len7 = l6;
                             _rt::cabi_dealloc(base7, len7 * 1, 1);
                         }
                         _ => {
-                            let l8 = *arg0.add(4).cast::<*mut u8>();
-                            let l9 = *arg0.add(8).cast::<usize>();
+                            let l8 = *arg0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l9 = *arg0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
                             _rt::cabi_dealloc(l8, l9, 1);
                         }
                     }
@@ -1093,36 +2417,42 @@ pub mod exports {
                 #[doc(hidden)]
                 macro_rules! __export_ntwk_theater_message_server_client_cabi {
                     ($ty:ident with_types_in $($path_to_types:tt)*) => {
-                        const _ : () = { #[export_name =
-                        "ntwk:theater/message-server-client#handle-send"] unsafe extern
+                        const _ : () = { #[unsafe (export_name =
+                        "ntwk:theater/message-server-client#handle-send")] unsafe extern
                         "C" fn export_handle_send(arg0 : i32, arg1 : * mut u8, arg2 :
-                        usize, arg3 : * mut u8, arg4 : usize,) -> * mut u8 {
+                        usize, arg3 : * mut u8, arg4 : usize,) -> * mut u8 { unsafe {
                         $($path_to_types)*:: _export_handle_send_cabi::<$ty > (arg0,
-                        arg1, arg2, arg3, arg4) } #[export_name =
-                        "cabi_post_ntwk:theater/message-server-client#handle-send"]
+                        arg1, arg2, arg3, arg4) } } #[unsafe (export_name =
+                        "cabi_post_ntwk:theater/message-server-client#handle-send")]
                         unsafe extern "C" fn _post_return_handle_send(arg0 : * mut u8,) {
-                        $($path_to_types)*:: __post_return_handle_send::<$ty > (arg0) }
-                        #[export_name =
-                        "ntwk:theater/message-server-client#handle-request"] unsafe
+                        unsafe { $($path_to_types)*:: __post_return_handle_send::<$ty >
+                        (arg0) } } #[unsafe (export_name =
+                        "ntwk:theater/message-server-client#handle-request")] unsafe
                         extern "C" fn export_handle_request(arg0 : i32, arg1 : * mut u8,
                         arg2 : usize, arg3 : * mut u8, arg4 : usize,) -> * mut u8 {
-                        $($path_to_types)*:: _export_handle_request_cabi::<$ty > (arg0,
-                        arg1, arg2, arg3, arg4) } #[export_name =
-                        "cabi_post_ntwk:theater/message-server-client#handle-request"]
+                        unsafe { $($path_to_types)*:: _export_handle_request_cabi::<$ty >
+                        (arg0, arg1, arg2, arg3, arg4) } } #[unsafe (export_name =
+                        "cabi_post_ntwk:theater/message-server-client#handle-request")]
                         unsafe extern "C" fn _post_return_handle_request(arg0 : * mut
-                        u8,) { $($path_to_types)*:: __post_return_handle_request::<$ty >
-                        (arg0) } };
+                        u8,) { unsafe { $($path_to_types)*::
+                        __post_return_handle_request::<$ty > (arg0) } } };
                     };
                 }
                 #[doc(hidden)]
                 pub(crate) use __export_ntwk_theater_message_server_client_cabi;
-                #[repr(align(4))]
-                struct _RetArea([::core::mem::MaybeUninit<u8>; 24]);
+                #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                struct _RetArea(
+                    [::core::mem::MaybeUninit<
+                        u8,
+                    >; 6 * ::core::mem::size_of::<*const u8>()],
+                );
                 static mut _RET_AREA: _RetArea = _RetArea(
-                    [::core::mem::MaybeUninit::uninit(); 24],
+                    [::core::mem::MaybeUninit::uninit(); 6
+                        * ::core::mem::size_of::<*const u8>()],
                 );
             }
-            #[allow(dead_code, clippy::all)]
+            #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
             pub mod actor {
                 #[used]
                 #[doc(hidden)]
@@ -1155,23 +2485,31 @@ pub mod exports {
                         },
                         (_rt::string_lift(bytes1),),
                     );
-                    let ptr3 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    let ptr3 = (&raw mut _RET_AREA.0).cast::<u8>();
                     match result2 {
                         Ok(e) => {
                             *ptr3.add(0).cast::<u8>() = (0i32) as u8;
                             let (t4_0,) = e;
                             match t4_0 {
                                 Some(e) => {
-                                    *ptr3.add(4).cast::<u8>() = (1i32) as u8;
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
                                     let vec5 = (e).into_boxed_slice();
                                     let ptr5 = vec5.as_ptr().cast::<u8>();
                                     let len5 = vec5.len();
                                     ::core::mem::forget(vec5);
-                                    *ptr3.add(12).cast::<usize>() = len5;
-                                    *ptr3.add(8).cast::<*mut u8>() = ptr5.cast_mut();
+                                    *ptr3
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len5;
+                                    *ptr3
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr5.cast_mut();
                                 }
                                 None => {
-                                    *ptr3.add(4).cast::<u8>() = (0i32) as u8;
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
                                 }
                             };
                         }
@@ -1181,8 +2519,12 @@ pub mod exports {
                             let ptr6 = vec6.as_ptr().cast::<u8>();
                             let len6 = vec6.len();
                             ::core::mem::forget(vec6);
-                            *ptr3.add(8).cast::<usize>() = len6;
-                            *ptr3.add(4).cast::<*mut u8>() = ptr6.cast_mut();
+                            *ptr3
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len6;
+                            *ptr3
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr6.cast_mut();
                         }
                     };
                     ptr3
@@ -1193,12 +2535,18 @@ pub mod exports {
                     let l0 = i32::from(*arg0.add(0).cast::<u8>());
                     match l0 {
                         0 => {
-                            let l1 = i32::from(*arg0.add(4).cast::<u8>());
+                            let l1 = i32::from(
+                                *arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                            );
                             match l1 {
                                 0 => {}
                                 _ => {
-                                    let l2 = *arg0.add(8).cast::<*mut u8>();
-                                    let l3 = *arg0.add(12).cast::<usize>();
+                                    let l2 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l3 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
                                     let base4 = l2;
                                     let len4 = l3;
                                     _rt::cabi_dealloc(base4, len4 * 1, 1);
@@ -1206,8 +2554,12 @@ pub mod exports {
                             }
                         }
                         _ => {
-                            let l5 = *arg0.add(4).cast::<*mut u8>();
-                            let l6 = *arg0.add(8).cast::<usize>();
+                            let l5 = *arg0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l6 = *arg0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
                             _rt::cabi_dealloc(l5, l6, 1);
                         }
                     }
@@ -1221,25 +2573,31 @@ pub mod exports {
                 #[doc(hidden)]
                 macro_rules! __export_ntwk_theater_actor_cabi {
                     ($ty:ident with_types_in $($path_to_types:tt)*) => {
-                        const _ : () = { #[export_name = "ntwk:theater/actor#init"]
-                        unsafe extern "C" fn export_init(arg0 : i32, arg1 : * mut u8,
-                        arg2 : usize, arg3 : * mut u8, arg4 : usize,) -> * mut u8 {
-                        $($path_to_types)*:: _export_init_cabi::<$ty > (arg0, arg1, arg2,
-                        arg3, arg4) } #[export_name =
-                        "cabi_post_ntwk:theater/actor#init"] unsafe extern "C" fn
-                        _post_return_init(arg0 : * mut u8,) { $($path_to_types)*::
-                        __post_return_init::<$ty > (arg0) } };
+                        const _ : () = { #[unsafe (export_name =
+                        "ntwk:theater/actor#init")] unsafe extern "C" fn export_init(arg0
+                        : i32, arg1 : * mut u8, arg2 : usize, arg3 : * mut u8, arg4 :
+                        usize,) -> * mut u8 { unsafe { $($path_to_types)*::
+                        _export_init_cabi::<$ty > (arg0, arg1, arg2, arg3, arg4) } }
+                        #[unsafe (export_name = "cabi_post_ntwk:theater/actor#init")]
+                        unsafe extern "C" fn _post_return_init(arg0 : * mut u8,) { unsafe
+                        { $($path_to_types)*:: __post_return_init::<$ty > (arg0) } } };
                     };
                 }
                 #[doc(hidden)]
                 pub(crate) use __export_ntwk_theater_actor_cabi;
-                #[repr(align(4))]
-                struct _RetArea([::core::mem::MaybeUninit<u8>; 16]);
+                #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                struct _RetArea(
+                    [::core::mem::MaybeUninit<
+                        u8,
+                    >; 4 * ::core::mem::size_of::<*const u8>()],
+                );
                 static mut _RET_AREA: _RetArea = _RetArea(
-                    [::core::mem::MaybeUninit::uninit(); 16],
+                    [::core::mem::MaybeUninit::uninit(); 4
+                        * ::core::mem::size_of::<*const u8>()],
                 );
             }
-            #[allow(dead_code, clippy::all)]
+            #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
             pub mod http_server {
                 #[used]
                 #[doc(hidden)]
@@ -1273,21 +2631,32 @@ pub mod exports {
                     let len9 = arg8;
                     let mut result9 = _rt::Vec::with_capacity(len9);
                     for i in 0..len9 {
-                        let base = base9.add(i * 16);
+                        let base = base9
+                            .add(i * (4 * ::core::mem::size_of::<*const u8>()));
                         let e9 = {
                             let l3 = *base.add(0).cast::<*mut u8>();
-                            let l4 = *base.add(4).cast::<usize>();
+                            let l4 = *base
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
                             let len5 = l4;
                             let bytes5 = _rt::Vec::from_raw_parts(l3.cast(), len5, len5);
-                            let l6 = *base.add(8).cast::<*mut u8>();
-                            let l7 = *base.add(12).cast::<usize>();
+                            let l6 = *base
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l7 = *base
+                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
                             let len8 = l7;
                             let bytes8 = _rt::Vec::from_raw_parts(l6.cast(), len8, len8);
                             (_rt::string_lift(bytes5), _rt::string_lift(bytes8))
                         };
                         result9.push(e9);
                     }
-                    _rt::cabi_dealloc(base9, len9 * 16, 4);
+                    _rt::cabi_dealloc(
+                        base9,
+                        len9 * (4 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
+                    );
                     let result11 = T::handle_request(
                         match arg0 {
                             0 => None,
@@ -1319,23 +2688,31 @@ pub mod exports {
                             },
                         ),
                     );
-                    let ptr12 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    let ptr12 = (&raw mut _RET_AREA.0).cast::<u8>();
                     match result11 {
                         Ok(e) => {
                             *ptr12.add(0).cast::<u8>() = (0i32) as u8;
                             let (t13_0, t13_1) = e;
                             match t13_0 {
                                 Some(e) => {
-                                    *ptr12.add(4).cast::<u8>() = (1i32) as u8;
+                                    *ptr12
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
                                     let vec14 = (e).into_boxed_slice();
                                     let ptr14 = vec14.as_ptr().cast::<u8>();
                                     let len14 = vec14.len();
                                     ::core::mem::forget(vec14);
-                                    *ptr12.add(12).cast::<usize>() = len14;
-                                    *ptr12.add(8).cast::<*mut u8>() = ptr14.cast_mut();
+                                    *ptr12
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len14;
+                                    *ptr12
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr14.cast_mut();
                                 }
                                 None => {
-                                    *ptr12.add(4).cast::<u8>() = (0i32) as u8;
+                                    *ptr12
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
                                 }
                             };
                             let (t15_0,) = t13_1;
@@ -1344,13 +2721,14 @@ pub mod exports {
                                 headers: headers16,
                                 body: body16,
                             } = t15_0;
-                            *ptr12.add(16).cast::<u16>() = (_rt::as_i32(status16))
-                                as u16;
+                            *ptr12
+                                .add(4 * ::core::mem::size_of::<*const u8>())
+                                .cast::<u16>() = (_rt::as_i32(status16)) as u16;
                             let vec20 = headers16;
                             let len20 = vec20.len();
                             let layout20 = _rt::alloc::Layout::from_size_align_unchecked(
-                                vec20.len() * 16,
-                                4,
+                                vec20.len() * (4 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
                             );
                             let result20 = if layout20.size() != 0 {
                                 let ptr = _rt::alloc::alloc(layout20).cast::<u8>();
@@ -1362,37 +2740,56 @@ pub mod exports {
                                 ::core::ptr::null_mut()
                             };
                             for (i, e) in vec20.into_iter().enumerate() {
-                                let base = result20.add(i * 16);
+                                let base = result20
+                                    .add(i * (4 * ::core::mem::size_of::<*const u8>()));
                                 {
                                     let (t17_0, t17_1) = e;
                                     let vec18 = (t17_0.into_bytes()).into_boxed_slice();
                                     let ptr18 = vec18.as_ptr().cast::<u8>();
                                     let len18 = vec18.len();
                                     ::core::mem::forget(vec18);
-                                    *base.add(4).cast::<usize>() = len18;
+                                    *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len18;
                                     *base.add(0).cast::<*mut u8>() = ptr18.cast_mut();
                                     let vec19 = (t17_1.into_bytes()).into_boxed_slice();
                                     let ptr19 = vec19.as_ptr().cast::<u8>();
                                     let len19 = vec19.len();
                                     ::core::mem::forget(vec19);
-                                    *base.add(12).cast::<usize>() = len19;
-                                    *base.add(8).cast::<*mut u8>() = ptr19.cast_mut();
+                                    *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len19;
+                                    *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr19.cast_mut();
                                 }
                             }
-                            *ptr12.add(24).cast::<usize>() = len20;
-                            *ptr12.add(20).cast::<*mut u8>() = result20;
+                            *ptr12
+                                .add(6 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len20;
+                            *ptr12
+                                .add(5 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = result20;
                             match body16 {
                                 Some(e) => {
-                                    *ptr12.add(28).cast::<u8>() = (1i32) as u8;
+                                    *ptr12
+                                        .add(7 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
                                     let vec21 = (e).into_boxed_slice();
                                     let ptr21 = vec21.as_ptr().cast::<u8>();
                                     let len21 = vec21.len();
                                     ::core::mem::forget(vec21);
-                                    *ptr12.add(36).cast::<usize>() = len21;
-                                    *ptr12.add(32).cast::<*mut u8>() = ptr21.cast_mut();
+                                    *ptr12
+                                        .add(9 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len21;
+                                    *ptr12
+                                        .add(8 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr21.cast_mut();
                                 }
                                 None => {
-                                    *ptr12.add(28).cast::<u8>() = (0i32) as u8;
+                                    *ptr12
+                                        .add(7 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
                                 }
                             };
                         }
@@ -1402,8 +2799,12 @@ pub mod exports {
                             let ptr22 = vec22.as_ptr().cast::<u8>();
                             let len22 = vec22.len();
                             ::core::mem::forget(vec22);
-                            *ptr12.add(8).cast::<usize>() = len22;
-                            *ptr12.add(4).cast::<*mut u8>() = ptr22.cast_mut();
+                            *ptr12
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len22;
+                            *ptr12
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr22.cast_mut();
                         }
                     };
                     ptr12
@@ -1414,39 +2815,68 @@ pub mod exports {
                     let l0 = i32::from(*arg0.add(0).cast::<u8>());
                     match l0 {
                         0 => {
-                            let l1 = i32::from(*arg0.add(4).cast::<u8>());
+                            let l1 = i32::from(
+                                *arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                            );
                             match l1 {
                                 0 => {}
                                 _ => {
-                                    let l2 = *arg0.add(8).cast::<*mut u8>();
-                                    let l3 = *arg0.add(12).cast::<usize>();
+                                    let l2 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l3 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
                                     let base4 = l2;
                                     let len4 = l3;
                                     _rt::cabi_dealloc(base4, len4 * 1, 1);
                                 }
                             }
-                            let l5 = *arg0.add(20).cast::<*mut u8>();
-                            let l6 = *arg0.add(24).cast::<usize>();
+                            let l5 = *arg0
+                                .add(5 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l6 = *arg0
+                                .add(6 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
                             let base11 = l5;
                             let len11 = l6;
                             for i in 0..len11 {
-                                let base = base11.add(i * 16);
+                                let base = base11
+                                    .add(i * (4 * ::core::mem::size_of::<*const u8>()));
                                 {
                                     let l7 = *base.add(0).cast::<*mut u8>();
-                                    let l8 = *base.add(4).cast::<usize>();
+                                    let l8 = *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
                                     _rt::cabi_dealloc(l7, l8, 1);
-                                    let l9 = *base.add(8).cast::<*mut u8>();
-                                    let l10 = *base.add(12).cast::<usize>();
+                                    let l9 = *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l10 = *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
                                     _rt::cabi_dealloc(l9, l10, 1);
                                 }
                             }
-                            _rt::cabi_dealloc(base11, len11 * 16, 4);
-                            let l12 = i32::from(*arg0.add(28).cast::<u8>());
+                            _rt::cabi_dealloc(
+                                base11,
+                                len11 * (4 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            );
+                            let l12 = i32::from(
+                                *arg0
+                                    .add(7 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<u8>(),
+                            );
                             match l12 {
                                 0 => {}
                                 _ => {
-                                    let l13 = *arg0.add(32).cast::<*mut u8>();
-                                    let l14 = *arg0.add(36).cast::<usize>();
+                                    let l13 = *arg0
+                                        .add(8 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l14 = *arg0
+                                        .add(9 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
                                     let base15 = l13;
                                     let len15 = l14;
                                     _rt::cabi_dealloc(base15, len15 * 1, 1);
@@ -1454,8 +2884,12 @@ pub mod exports {
                             }
                         }
                         _ => {
-                            let l16 = *arg0.add(4).cast::<*mut u8>();
-                            let l17 = *arg0.add(8).cast::<usize>();
+                            let l16 = *arg0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l17 = *arg0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
                             _rt::cabi_dealloc(l16, l17, 1);
                         }
                     }
@@ -1469,26 +2903,512 @@ pub mod exports {
                 #[doc(hidden)]
                 macro_rules! __export_ntwk_theater_http_server_cabi {
                     ($ty:ident with_types_in $($path_to_types:tt)*) => {
-                        const _ : () = { #[export_name =
-                        "ntwk:theater/http-server#handle-request"] unsafe extern "C" fn
+                        const _ : () = { #[unsafe (export_name =
+                        "ntwk:theater/http-server#handle-request")] unsafe extern "C" fn
                         export_handle_request(arg0 : i32, arg1 : * mut u8, arg2 : usize,
                         arg3 : * mut u8, arg4 : usize, arg5 : * mut u8, arg6 : usize,
                         arg7 : * mut u8, arg8 : usize, arg9 : i32, arg10 : * mut u8,
-                        arg11 : usize,) -> * mut u8 { $($path_to_types)*::
+                        arg11 : usize,) -> * mut u8 { unsafe { $($path_to_types)*::
                         _export_handle_request_cabi::<$ty > (arg0, arg1, arg2, arg3,
-                        arg4, arg5, arg6, arg7, arg8, arg9, arg10, arg11) } #[export_name
-                        = "cabi_post_ntwk:theater/http-server#handle-request"] unsafe
+                        arg4, arg5, arg6, arg7, arg8, arg9, arg10, arg11) } } #[unsafe
+                        (export_name =
+                        "cabi_post_ntwk:theater/http-server#handle-request")] unsafe
                         extern "C" fn _post_return_handle_request(arg0 : * mut u8,) {
-                        $($path_to_types)*:: __post_return_handle_request::<$ty > (arg0)
-                        } };
+                        unsafe { $($path_to_types)*:: __post_return_handle_request::<$ty
+                        > (arg0) } } };
                     };
                 }
                 #[doc(hidden)]
                 pub(crate) use __export_ntwk_theater_http_server_cabi;
-                #[repr(align(4))]
-                struct _RetArea([::core::mem::MaybeUninit<u8>; 40]);
+                #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                struct _RetArea(
+                    [::core::mem::MaybeUninit<
+                        u8,
+                    >; 10 * ::core::mem::size_of::<*const u8>()],
+                );
+                static mut _RET_AREA: _RetArea = _RetArea(
+                    [::core::mem::MaybeUninit::uninit(); 10
+                        * ::core::mem::size_of::<*const u8>()],
+                );
+            }
+            /// Interface for handling WebSocket connections and messages
+            #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+            pub mod websocket_server {
+                #[used]
+                #[doc(hidden)]
+                static __FORCE_SECTION_REF: fn() = super::super::super::super::__link_custom_section_describing_imports;
+                use super::super::super::super::_rt;
+                pub type State = super::super::super::super::ntwk::theater::types::State;
+                /// The type of WebSocket message/event
+                #[derive(Clone)]
+                pub enum MessageType {
+                    /// A text message
+                    Text,
+                    /// A binary message
+                    Binary,
+                    /// A new connection was established
+                    Connect,
+                    /// The connection was closed
+                    Close,
+                    /// A ping message (for keep-alive)
+                    Ping,
+                    /// A pong message (response to ping)
+                    Pong,
+                    /// Any other message type
+                    Other(_rt::String),
+                }
+                impl ::core::fmt::Debug for MessageType {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        match self {
+                            MessageType::Text => {
+                                f.debug_tuple("MessageType::Text").finish()
+                            }
+                            MessageType::Binary => {
+                                f.debug_tuple("MessageType::Binary").finish()
+                            }
+                            MessageType::Connect => {
+                                f.debug_tuple("MessageType::Connect").finish()
+                            }
+                            MessageType::Close => {
+                                f.debug_tuple("MessageType::Close").finish()
+                            }
+                            MessageType::Ping => {
+                                f.debug_tuple("MessageType::Ping").finish()
+                            }
+                            MessageType::Pong => {
+                                f.debug_tuple("MessageType::Pong").finish()
+                            }
+                            MessageType::Other(e) => {
+                                f.debug_tuple("MessageType::Other").field(e).finish()
+                            }
+                        }
+                    }
+                }
+                /// Represents a message sent or received over a WebSocket connection
+                #[derive(Clone)]
+                pub struct WebsocketMessage {
+                    /// The type of the message
+                    pub ty: MessageType,
+                    /// Binary data payload (used for binary messages)
+                    pub data: Option<_rt::Vec<u8>>,
+                    /// Text payload (used for text messages)
+                    pub text: Option<_rt::String>,
+                }
+                impl ::core::fmt::Debug for WebsocketMessage {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        f.debug_struct("WebsocketMessage")
+                            .field("ty", &self.ty)
+                            .field("data", &self.data)
+                            .field("text", &self.text)
+                            .finish()
+                    }
+                }
+                /// Response containing messages to send back over the WebSocket
+                #[derive(Clone)]
+                pub struct WebsocketResponse {
+                    /// List of messages to send back to the client
+                    pub messages: _rt::Vec<WebsocketMessage>,
+                }
+                impl ::core::fmt::Debug for WebsocketResponse {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        f.debug_struct("WebsocketResponse")
+                            .field("messages", &self.messages)
+                            .finish()
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_handle_message_cabi<T: Guest>(
+                    arg0: i32,
+                    arg1: *mut u8,
+                    arg2: usize,
+                    arg3: i32,
+                    arg4: *mut u8,
+                    arg5: usize,
+                    arg6: i32,
+                    arg7: *mut u8,
+                    arg8: usize,
+                    arg9: i32,
+                    arg10: *mut u8,
+                    arg11: usize,
+                ) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let v2 = match arg3 {
+                        0 => MessageType::Text,
+                        1 => MessageType::Binary,
+                        2 => MessageType::Connect,
+                        3 => MessageType::Close,
+                        4 => MessageType::Ping,
+                        5 => MessageType::Pong,
+                        n => {
+                            debug_assert_eq!(n, 6, "invalid enum discriminant");
+                            let e2 = {
+                                let len1 = arg5;
+                                let bytes1 = _rt::Vec::from_raw_parts(
+                                    arg4.cast(),
+                                    len1,
+                                    len1,
+                                );
+                                _rt::string_lift(bytes1)
+                            };
+                            MessageType::Other(e2)
+                        }
+                    };
+                    let result5 = T::handle_message(
+                        match arg0 {
+                            0 => None,
+                            1 => {
+                                let e = {
+                                    let len0 = arg2;
+                                    _rt::Vec::from_raw_parts(arg1.cast(), len0, len0)
+                                };
+                                Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        },
+                        (
+                            WebsocketMessage {
+                                ty: v2,
+                                data: match arg6 {
+                                    0 => None,
+                                    1 => {
+                                        let e = {
+                                            let len3 = arg8;
+                                            _rt::Vec::from_raw_parts(arg7.cast(), len3, len3)
+                                        };
+                                        Some(e)
+                                    }
+                                    _ => _rt::invalid_enum_discriminant(),
+                                },
+                                text: match arg9 {
+                                    0 => None,
+                                    1 => {
+                                        let e = {
+                                            let len4 = arg11;
+                                            let bytes4 = _rt::Vec::from_raw_parts(
+                                                arg10.cast(),
+                                                len4,
+                                                len4,
+                                            );
+                                            _rt::string_lift(bytes4)
+                                        };
+                                        Some(e)
+                                    }
+                                    _ => _rt::invalid_enum_discriminant(),
+                                },
+                            },
+                        ),
+                    );
+                    let ptr6 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    match result5 {
+                        Ok(e) => {
+                            *ptr6.add(0).cast::<u8>() = (0i32) as u8;
+                            let (t7_0, t7_1) = e;
+                            match t7_0 {
+                                Some(e) => {
+                                    *ptr6
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                    let vec8 = (e).into_boxed_slice();
+                                    let ptr8 = vec8.as_ptr().cast::<u8>();
+                                    let len8 = vec8.len();
+                                    ::core::mem::forget(vec8);
+                                    *ptr6
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len8;
+                                    *ptr6
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr8.cast_mut();
+                                }
+                                None => {
+                                    *ptr6
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                }
+                            };
+                            let (t9_0,) = t7_1;
+                            let WebsocketResponse { messages: messages10 } = t9_0;
+                            let vec15 = messages10;
+                            let len15 = vec15.len();
+                            let layout15 = _rt::alloc::Layout::from_size_align_unchecked(
+                                vec15.len() * (9 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            );
+                            let result15 = if layout15.size() != 0 {
+                                let ptr = _rt::alloc::alloc(layout15).cast::<u8>();
+                                if ptr.is_null() {
+                                    _rt::alloc::handle_alloc_error(layout15);
+                                }
+                                ptr
+                            } else {
+                                ::core::ptr::null_mut()
+                            };
+                            for (i, e) in vec15.into_iter().enumerate() {
+                                let base = result15
+                                    .add(i * (9 * ::core::mem::size_of::<*const u8>()));
+                                {
+                                    let WebsocketMessage {
+                                        ty: ty11,
+                                        data: data11,
+                                        text: text11,
+                                    } = e;
+                                    match ty11 {
+                                        MessageType::Text => {
+                                            *base.add(0).cast::<u8>() = (0i32) as u8;
+                                        }
+                                        MessageType::Binary => {
+                                            *base.add(0).cast::<u8>() = (1i32) as u8;
+                                        }
+                                        MessageType::Connect => {
+                                            *base.add(0).cast::<u8>() = (2i32) as u8;
+                                        }
+                                        MessageType::Close => {
+                                            *base.add(0).cast::<u8>() = (3i32) as u8;
+                                        }
+                                        MessageType::Ping => {
+                                            *base.add(0).cast::<u8>() = (4i32) as u8;
+                                        }
+                                        MessageType::Pong => {
+                                            *base.add(0).cast::<u8>() = (5i32) as u8;
+                                        }
+                                        MessageType::Other(e) => {
+                                            *base.add(0).cast::<u8>() = (6i32) as u8;
+                                            let vec12 = (e.into_bytes()).into_boxed_slice();
+                                            let ptr12 = vec12.as_ptr().cast::<u8>();
+                                            let len12 = vec12.len();
+                                            ::core::mem::forget(vec12);
+                                            *base
+                                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len12;
+                                            *base
+                                                .add(::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>() = ptr12.cast_mut();
+                                        }
+                                    }
+                                    match data11 {
+                                        Some(e) => {
+                                            *base
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                            let vec13 = (e).into_boxed_slice();
+                                            let ptr13 = vec13.as_ptr().cast::<u8>();
+                                            let len13 = vec13.len();
+                                            ::core::mem::forget(vec13);
+                                            *base
+                                                .add(5 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len13;
+                                            *base
+                                                .add(4 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>() = ptr13.cast_mut();
+                                        }
+                                        None => {
+                                            *base
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match text11 {
+                                        Some(e) => {
+                                            *base
+                                                .add(6 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                            let vec14 = (e.into_bytes()).into_boxed_slice();
+                                            let ptr14 = vec14.as_ptr().cast::<u8>();
+                                            let len14 = vec14.len();
+                                            ::core::mem::forget(vec14);
+                                            *base
+                                                .add(8 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len14;
+                                            *base
+                                                .add(7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>() = ptr14.cast_mut();
+                                        }
+                                        None => {
+                                            *base
+                                                .add(6 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                }
+                            }
+                            *ptr6
+                                .add(5 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len15;
+                            *ptr6
+                                .add(4 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = result15;
+                        }
+                        Err(e) => {
+                            *ptr6.add(0).cast::<u8>() = (1i32) as u8;
+                            let vec16 = (e.into_bytes()).into_boxed_slice();
+                            let ptr16 = vec16.as_ptr().cast::<u8>();
+                            let len16 = vec16.len();
+                            ::core::mem::forget(vec16);
+                            *ptr6
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len16;
+                            *ptr6
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr16.cast_mut();
+                        }
+                    };
+                    ptr6
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_handle_message<T: Guest>(arg0: *mut u8) {
+                    let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                    match l0 {
+                        0 => {
+                            let l1 = i32::from(
+                                *arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                            );
+                            match l1 {
+                                0 => {}
+                                _ => {
+                                    let l2 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l3 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let base4 = l2;
+                                    let len4 = l3;
+                                    _rt::cabi_dealloc(base4, len4 * 1, 1);
+                                }
+                            }
+                            let l5 = *arg0
+                                .add(4 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l6 = *arg0
+                                .add(5 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let base17 = l5;
+                            let len17 = l6;
+                            for i in 0..len17 {
+                                let base = base17
+                                    .add(i * (9 * ::core::mem::size_of::<*const u8>()));
+                                {
+                                    let l7 = i32::from(*base.add(0).cast::<u8>());
+                                    match l7 {
+                                        0 => {}
+                                        1 => {}
+                                        2 => {}
+                                        3 => {}
+                                        4 => {}
+                                        5 => {}
+                                        _ => {
+                                            let l8 = *base
+                                                .add(::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l9 = *base
+                                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            _rt::cabi_dealloc(l8, l9, 1);
+                                        }
+                                    }
+                                    let l10 = i32::from(
+                                        *base
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>(),
+                                    );
+                                    match l10 {
+                                        0 => {}
+                                        _ => {
+                                            let l11 = *base
+                                                .add(4 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l12 = *base
+                                                .add(5 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            let base13 = l11;
+                                            let len13 = l12;
+                                            _rt::cabi_dealloc(base13, len13 * 1, 1);
+                                        }
+                                    }
+                                    let l14 = i32::from(
+                                        *base
+                                            .add(6 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>(),
+                                    );
+                                    match l14 {
+                                        0 => {}
+                                        _ => {
+                                            let l15 = *base
+                                                .add(7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l16 = *base
+                                                .add(8 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            _rt::cabi_dealloc(l15, l16, 1);
+                                        }
+                                    }
+                                }
+                            }
+                            _rt::cabi_dealloc(
+                                base17,
+                                len17 * (9 * ::core::mem::size_of::<*const u8>()),
+                                ::core::mem::size_of::<*const u8>(),
+                            );
+                        }
+                        _ => {
+                            let l18 = *arg0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l19 = *arg0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            _rt::cabi_dealloc(l18, l19, 1);
+                        }
+                    }
+                }
+                pub trait Guest {
+                    /// Called for each event on the WebSocket (connections, messages, disconnections)
+                    fn handle_message(
+                        state: State,
+                        params: (WebsocketMessage,),
+                    ) -> Result<(State, (WebsocketResponse,)), _rt::String>;
+                }
+                #[doc(hidden)]
+                macro_rules! __export_ntwk_theater_websocket_server_cabi {
+                    ($ty:ident with_types_in $($path_to_types:tt)*) => {
+                        const _ : () = { #[unsafe (export_name =
+                        "ntwk:theater/websocket-server#handle-message")] unsafe extern
+                        "C" fn export_handle_message(arg0 : i32, arg1 : * mut u8, arg2 :
+                        usize, arg3 : i32, arg4 : * mut u8, arg5 : usize, arg6 : i32,
+                        arg7 : * mut u8, arg8 : usize, arg9 : i32, arg10 : * mut u8,
+                        arg11 : usize,) -> * mut u8 { unsafe { $($path_to_types)*::
+                        _export_handle_message_cabi::<$ty > (arg0, arg1, arg2, arg3,
+                        arg4, arg5, arg6, arg7, arg8, arg9, arg10, arg11) } } #[unsafe
+                        (export_name =
+                        "cabi_post_ntwk:theater/websocket-server#handle-message")] unsafe
+                        extern "C" fn _post_return_handle_message(arg0 : * mut u8,) {
+                        unsafe { $($path_to_types)*:: __post_return_handle_message::<$ty
+                        > (arg0) } } };
+                    };
+                }
+                #[doc(hidden)]
+                pub(crate) use __export_ntwk_theater_websocket_server_cabi;
+                #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                struct _RetArea(
+                    [::core::mem::MaybeUninit<
+                        u8,
+                    >; 6 * ::core::mem::size_of::<*const u8>()],
+                );
                 static mut _RET_AREA: _RetArea = _RetArea(
-                    [::core::mem::MaybeUninit::uninit(); 40],
+                    [::core::mem::MaybeUninit::uninit(); 6
+                        * ::core::mem::size_of::<*const u8>()],
                 );
             }
         }
@@ -1496,6 +3416,7 @@ pub mod exports {
 }
 #[rustfmt::skip]
 mod _rt {
+    #![allow(dead_code, clippy::all)]
     pub use alloc_crate::vec::Vec;
     pub use alloc_crate::string::String;
     pub unsafe fn string_lift(bytes: Vec<u8>) -> String {
@@ -1509,7 +3430,7 @@ mod _rt {
         if cfg!(debug_assertions) {
             panic!("invalid enum discriminant")
         } else {
-            core::hint::unreachable_unchecked()
+            unsafe { core::hint::unreachable_unchecked() }
         }
     }
     pub unsafe fn cabi_dealloc(ptr: *mut u8, size: usize, align: usize) {
@@ -1596,8 +3517,8 @@ mod _rt {
     }
     extern crate alloc as alloc_crate;
 }
-/// Generates `#[no_mangle]` functions to export the specified type as the
-/// root implementation of all generated traits.
+/// Generates `#[unsafe(no_mangle)]` functions to export the specified type as
+/// the root implementation of all generated traits.
 ///
 /// For more information see the documentation of `wit_bindgen::generate!`.
 ///
@@ -1628,16 +3549,23 @@ macro_rules! __export_single_chat_impl {
         $($path_to_types_root)*::
         exports::ntwk::theater::http_server::__export_ntwk_theater_http_server_cabi!($ty
         with_types_in $($path_to_types_root)*:: exports::ntwk::theater::http_server);
+        $($path_to_types_root)*::
+        exports::ntwk::theater::websocket_server::__export_ntwk_theater_websocket_server_cabi!($ty
+        with_types_in $($path_to_types_root)*::
+        exports::ntwk::theater::websocket_server);
     };
 }
 #[doc(inline)]
 pub(crate) use __export_single_chat_impl as export;
 #[cfg(target_arch = "wasm32")]
-#[link_section = "component-type:wit-bindgen:0.36.0:ntwk:theater:single-chat:encoded world"]
+#[unsafe(
+    link_section = "component-type:wit-bindgen:0.41.0:ntwk:theater:single-chat:encoded world"
+)]
 #[doc(hidden)]
-pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 1603] = *b"\
-\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xc1\x0b\x01A\x02\x01\
-A\x19\x01B\x0f\x01p}\x04\0\x04json\x03\0\0\x01p}\x01k\x02\x04\0\x05state\x03\0\x03\
+#[allow(clippy::octal_escapes)]
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 2344] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xa6\x11\x01A\x02\x01\
+A\x1d\x01B\x0f\x01p}\x04\0\x04json\x03\0\0\x01p}\x01k\x02\x04\0\x05state\x03\0\x03\
 \x01s\x04\0\x08actor-id\x03\0\x05\x01kw\x01r\x03\x0aevent-types\x06parent\x07\x04\
 data\x01\x04\0\x05event\x03\0\x08\x01r\x02\x04hashw\x05event\x09\x04\0\x0ameta-e\
 vent\x03\0\x0a\x01p\x0b\x01r\x01\x06events\x0c\x04\0\x05chain\x03\0\x0d\x03\0\x12\
@@ -1651,32 +3579,49 @@ ntwk:theater/runtime\x05\x04\x01B\x12\x01p}\x01j\x01\0\x01s\x01@\x01\x04paths\0\
 list-files\x01\x07\x01@\x01\x04paths\0\x03\x04\0\x0bdelete-file\x01\x08\x04\0\x0a\
 create-dir\x01\x08\x04\0\x0adelete-dir\x01\x08\x01j\x01\x7f\x01s\x01@\x01\x04pat\
 hs\0\x09\x04\0\x0bpath-exists\x01\x0a\x03\0\x17ntwk:theater/filesystem\x05\x05\x01\
-B\x0a\x02\x03\x02\x01\x01\x04\0\x04json\x03\0\0\x02\x03\x02\x01\x03\x04\0\x08act\
-or-id\x03\0\x02\x01j\0\x01s\x01@\x02\x08actor-id\x03\x03msg\x01\0\x04\x04\0\x04s\
-end\x01\x05\x01j\x01\x01\x01s\x01@\x02\x08actor-id\x03\x03msg\x01\0\x06\x04\0\x07\
-request\x01\x07\x03\0\x20ntwk:theater/message-server-host\x05\x06\x01B\x09\x01p}\
-\x04\0\x05bytes\x03\0\0\x01o\x02ss\x01p\x02\x01k\x01\x01r\x04\x06methods\x03uris\
-\x07headers\x03\x04body\x04\x04\0\x0chttp-request\x03\0\x05\x01r\x03\x06status{\x07\
-headers\x03\x04body\x04\x04\0\x0dhttp-response\x03\0\x07\x03\0\x17ntwk:theater/h\
-ttp-types\x05\x07\x02\x03\0\x04\x0chttp-request\x02\x03\0\x04\x0dhttp-response\x01\
-B\x09\x02\x03\x02\x01\x01\x04\0\x04json\x03\0\0\x02\x03\x02\x01\x08\x04\0\x0chtt\
-p-request\x03\0\x02\x02\x03\x02\x01\x09\x04\0\x0dhttp-response\x03\0\x04\x01j\x01\
+B#\x01r\x01\x04hashs\x04\0\x0bcontent-ref\x03\0\0\x01p}\x01j\x01\x01\x01s\x01@\x01\
+\x07content\x02\0\x03\x04\0\x05store\x01\x04\x01j\x01\x02\x01s\x01@\x01\x0bconte\
+nt-ref\x01\0\x05\x04\0\x03get\x01\x06\x01j\x01\x7f\x01s\x01@\x01\x0bcontent-ref\x01\
+\0\x07\x04\0\x06exists\x01\x08\x01j\0\x01s\x01@\x02\x05labels\x0bcontent-ref\x01\
+\0\x09\x04\0\x05label\x01\x0a\x01p\x01\x01j\x01\x0b\x01s\x01@\x01\x05labels\0\x0c\
+\x04\0\x0cget-by-label\x01\x0d\x01@\x01\x05labels\0\x09\x04\0\x0cremove-label\x01\
+\x0e\x04\0\x11remove-from-label\x01\x0a\x01@\x02\x05labels\x07content\x02\0\x03\x04\
+\0\x0cput-at-label\x01\x0f\x04\0\x18replace-content-at-label\x01\x0f\x04\0\x10re\
+place-at-label\x01\x0a\x01ps\x01j\x01\x10\x01s\x01@\0\0\x11\x04\0\x0blist-labels\
+\x01\x12\x01@\0\0\x0c\x04\0\x10list-all-content\x01\x13\x01j\x01w\x01s\x01@\0\0\x14\
+\x04\0\x14calculate-total-size\x01\x15\x03\0\x12ntwk:theater/store\x05\x06\x01B\x0a\
+\x02\x03\x02\x01\x01\x04\0\x04json\x03\0\0\x02\x03\x02\x01\x03\x04\0\x08actor-id\
+\x03\0\x02\x01j\0\x01s\x01@\x02\x08actor-id\x03\x03msg\x01\0\x04\x04\0\x04send\x01\
+\x05\x01j\x01\x01\x01s\x01@\x02\x08actor-id\x03\x03msg\x01\0\x06\x04\0\x07reques\
+t\x01\x07\x03\0\x20ntwk:theater/message-server-host\x05\x07\x01B\x09\x01p}\x04\0\
+\x05bytes\x03\0\0\x01o\x02ss\x01p\x02\x01k\x01\x01r\x04\x06methods\x03uris\x07he\
+aders\x03\x04body\x04\x04\0\x0chttp-request\x03\0\x05\x01r\x03\x06status{\x07hea\
+ders\x03\x04body\x04\x04\0\x0dhttp-response\x03\0\x07\x03\0\x17ntwk:theater/http\
+-types\x05\x08\x02\x03\0\x05\x0chttp-request\x02\x03\0\x05\x0dhttp-response\x01B\
+\x09\x02\x03\x02\x01\x01\x04\0\x04json\x03\0\0\x02\x03\x02\x01\x09\x04\0\x0chttp\
+-request\x03\0\x02\x02\x03\x02\x01\x0a\x04\0\x0dhttp-response\x03\0\x04\x01j\x01\
 \x05\x01s\x01@\x01\x03req\x03\0\x06\x04\0\x09send-http\x01\x07\x03\0\x18ntwk:the\
-ater/http-client\x05\x0a\x02\x03\0\0\x05event\x01B\x0e\x02\x03\x02\x01\x01\x04\0\
-\x04json\x03\0\0\x02\x03\x02\x01\x0b\x04\0\x05event\x03\0\x02\x01k\x01\x01o\x01\x01\
+ater/http-client\x05\x0b\x02\x03\0\0\x05event\x01B\x0e\x02\x03\x02\x01\x01\x04\0\
+\x04json\x03\0\0\x02\x03\x02\x01\x0c\x04\0\x05event\x03\0\x02\x01k\x01\x01o\x01\x01\
 \x01o\x01\x04\x01j\x01\x06\x01s\x01@\x02\x05state\x04\x06params\x05\0\x07\x04\0\x0b\
 handle-send\x01\x08\x01o\x02\x04\x05\x01j\x01\x09\x01s\x01@\x02\x05state\x04\x06\
 params\x05\0\x0a\x04\0\x0ehandle-request\x01\x0b\x04\0\"ntwk:theater/message-ser\
-ver-client\x05\x0c\x02\x03\0\0\x05state\x01B\x07\x02\x03\x02\x01\x0d\x04\0\x05st\
+ver-client\x05\x0d\x02\x03\0\0\x05state\x01B\x07\x02\x03\x02\x01\x0e\x04\0\x05st\
 ate\x03\0\0\x01o\x01s\x01o\x01\x01\x01j\x01\x03\x01s\x01@\x02\x05state\x01\x06pa\
-rams\x02\0\x04\x04\0\x04init\x01\x05\x04\0\x12ntwk:theater/actor\x05\x0e\x01B\x0c\
-\x02\x03\x02\x01\x0d\x04\0\x05state\x03\0\0\x02\x03\x02\x01\x08\x04\0\x0chttp-re\
-quest\x03\0\x02\x02\x03\x02\x01\x09\x04\0\x0dhttp-response\x03\0\x04\x01o\x01\x03\
+rams\x02\0\x04\x04\0\x04init\x01\x05\x04\0\x12ntwk:theater/actor\x05\x0f\x01B\x0c\
+\x02\x03\x02\x01\x0e\x04\0\x05state\x03\0\0\x02\x03\x02\x01\x09\x04\0\x0chttp-re\
+quest\x03\0\x02\x02\x03\x02\x01\x0a\x04\0\x0dhttp-response\x03\0\x04\x01o\x01\x03\
 \x01o\x01\x05\x01o\x02\x01\x07\x01j\x01\x08\x01s\x01@\x02\x05state\x01\x06params\
 \x06\0\x09\x04\0\x0ehandle-request\x01\x0a\x04\0\x18ntwk:theater/http-server\x05\
-\x0f\x04\0\x18ntwk:theater/single-chat\x04\0\x0b\x11\x01\0\x0bsingle-chat\x03\0\0\
-\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-component\x070.220.1\x10wit-bind\
-gen-rust\x060.36.0";
+\x10\x01B\x12\x02\x03\x02\x01\x0e\x04\0\x05state\x03\0\0\x01q\x07\x04text\0\0\x06\
+binary\0\0\x07connect\0\0\x05close\0\0\x04ping\0\0\x04pong\0\0\x05other\x01s\0\x04\
+\0\x0cmessage-type\x03\0\x02\x01p}\x01k\x04\x01ks\x01r\x03\x02ty\x03\x04data\x05\
+\x04text\x06\x04\0\x11websocket-message\x03\0\x07\x01p\x08\x01r\x01\x08messages\x09\
+\x04\0\x12websocket-response\x03\0\x0a\x01o\x01\x08\x01o\x01\x0b\x01o\x02\x01\x0d\
+\x01j\x01\x0e\x01s\x01@\x02\x05state\x01\x06params\x0c\0\x0f\x04\0\x0ehandle-mes\
+sage\x01\x10\x04\0\x1dntwk:theater/websocket-server\x05\x11\x04\0\x18ntwk:theate\
+r/single-chat\x04\0\x0b\x11\x01\0\x0bsingle-chat\x03\0\0\0G\x09producers\x01\x0c\
+processed-by\x02\x0dwit-component\x070.227.1\x10wit-bindgen-rust\x060.41.0";
 #[inline(never)]
 #[doc(hidden)]
 pub fn __link_custom_section_describing_imports() {
@@ -0,0 +1,360 @@
+//! Copy of each store's entries, so that listing and searching don't need an
+//! `All` request to the store actor every time.
+//!
+//! The copies live in the actor instance's memory rather than in its state,
+//! so they are neither persisted nor re-serialized on every call; if the
+//! instance is recreated the cache simply starts empty.
+//!
+//! The actor has no clock. A copy is only used for HTTP requests that send
+//! their time in `X-Client-Time`, and only while that time is within
+//! `cache.entries_ttl_ms` after the listing was made. A time before the
+//! listing (a skewed client clock) counts as expired, and every copy serves
+//! at most `cache.entries_max_uses` lookups, so no header value can keep a
+//! copy alive indefinitely. Requests without a time (websocket polls, actor
+//! messages) always go to the store; their listings refresh an existing copy
+//! but neither create one nor extend its lifetime.
+//!
+//! Copies are also dropped when the visualizer writes to the store or when a
+//! notification announces a change without its value; notifications that
+//! carry the value update the copy in place.
+
+use crate::caching::CacheConfig;
+use crate::listing::Entry;
+use crate::time;
+use serde_json::{json, Value};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+#[derive(Debug)]
+struct CachedStore {
+    /// Client time of the request that made the listing.
+    loaded_at: u64,
+    /// Lookups served so far.
+    uses: u64,
+    size: usize,
+    entries: BTreeMap<String, Vec<u8>>,
+}
+
+impl CachedStore {
+    fn is_fresh(&self, now: u64, config: &CacheConfig) -> bool {
+        now >= self.loaded_at
+            && now - self.loaded_at < config.entries_ttl_ms
+            && self.uses < config.entries_max_uses
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Lookups that skipped the cache for lack of a client time.
+    pub bypassed: u64,
+    pub invalidations: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct EntryCache {
+    stores: BTreeMap<String, CachedStore>,
+    stats: CacheStats,
+}
+
+thread_local! {
+    static CACHE: RefCell<EntryCache> = RefCell::new(EntryCache::default());
+}
+
+/// Runs `f` on the instance's cache.
+pub fn with<R>(f: impl FnOnce(&mut EntryCache) -> R) -> R {
+    CACHE.with(|cache| f(&mut cache.borrow_mut()))
+}
+
+impl EntryCache {
+    /// The copy of `store_id` if it may serve a request made at `now`.
+    /// Counts a hit, a miss or a bypass.
+    fn lookup(
+        &mut self,
+        store_id: &str,
+        now: Option<u64>,
+        config: &CacheConfig,
+    ) -> Option<&CachedStore> {
+        if config.entries_ttl_ms == 0 {
+            return None;
+        }
+        let Some(now) = now else {
+            self.stats.bypassed += 1;
+            return None;
+        };
+        match self.stores.get_mut(store_id) {
+            Some(store) if store.is_fresh(now, config) => {
+                store.uses += 1;
+                self.stats.hits += 1;
+                Some(store)
+            }
+            _ => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// All entries of `store_id`, sorted by key, if the copy is fresh.
+    pub fn entries(
+        &mut self,
+        store_id: &str,
+        now: Option<u64>,
+        config: &CacheConfig,
+    ) -> Option<Vec<Entry>> {
+        let store = self.lookup(store_id, now, config)?;
+        Some(
+            store
+                .entries
+                .iter()
+                .map(|(key, value)| Entry {
+                    key: key.clone(),
+                    value: value.clone(),
+                })
+                .collect(),
+        )
+    }
+
+    /// The value under `key` (`Some(None)` if there is none), if the copy of
+    /// `store_id` is fresh.
+    pub fn entry(
+        &mut self,
+        store_id: &str,
+        key: &str,
+        now: Option<u64>,
+        config: &CacheConfig,
+    ) -> Option<Option<Vec<u8>>> {
+        let store = self.lookup(store_id, now, config)?;
+        Some(store.entries.get(key).cloned())
+    }
+
+    /// Replaces the copy of `store_id` with a listing made at `now`. Without
+    /// a time, an existing copy takes the listing's values but keeps its
+    /// `loaded_at` and uses, and no new copy is made. Stores whose values
+    /// exceed `entries_max_bytes` are not cached at all.
+    pub fn fill(
+        &mut self,
+        store_id: &str,
+        entries: &[Entry],
+        now: Option<u64>,
+        config: &CacheConfig,
+    ) {
+        let size: usize = entries.iter().map(|e| e.value.len()).sum();
+        if config.entries_ttl_ms == 0 || size > config.entries_max_bytes {
+            self.stores.remove(store_id);
+            return;
+        }
+        let (loaded_at, uses) = match (now, self.stores.get(store_id)) {
+            (Some(now), _) => (now, 0),
+            (None, Some(existing)) => (existing.loaded_at, existing.uses),
+            (None, None) => return,
+        };
+        let entries = entries
+            .iter()
+            .map(|e| (e.key.clone(), e.value.clone()))
+            .collect();
+        self.stores.insert(
+            store_id.to_string(),
+            CachedStore {
+                loaded_at,
+                uses,
+                size,
+                entries,
+            },
+        );
+    }
+
+    /// Drops the copy of `store_id`.
+    pub fn invalidate(&mut self, store_id: &str) {
+        if self.stores.remove(store_id).is_some() {
+            self.stats.invalidations += 1;
+        }
+    }
+
+    /// Applies a change whose outcome is known: the new value of `key`, or
+    /// `None` if it was removed.
+    pub fn update(&mut self, store_id: &str, key: &str, value: Option<&[u8]>) {
+        let Some(store) = self.stores.get_mut(store_id) else {
+            return;
+        };
+        let old = match value {
+            Some(value) => store.entries.insert(key.to_string(), value.to_vec()),
+            None => store.entries.remove(key),
+        };
+        store.size = store.size - old.map_or(0, |v| v.len()) + value.map_or(0, <[u8]>::len);
+    }
+
+    /// Drops every copy. The counters are kept.
+    pub fn clear(&mut self) {
+        let stores = std::mem::take(&mut self.stores);
+        self.stats.invalidations += stores.len() as u64;
+    }
+
+    /// Counters and cached stores. Ages are relative to `now`, if known.
+    pub fn to_json(&self, now: Option<u64>, config: &CacheConfig) -> Value {
+        let stats = self.stats;
+        let lookups = stats.hits + stats.misses;
+        let stores: Vec<Value> = self
+            .stores
+            .iter()
+            .map(|(store_id, store)| {
+                json!({
+                    "store_id": store_id,
+                    "entry_count": store.entries.len(),
+                    "total_size": store.size,
+                    "loaded_at": time::to_rfc3339(store.loaded_at),
+                    "uses": store.uses,
+                    "age_ms": now.and_then(|now| now.checked_sub(store.loaded_at)),
+                    "fresh": now.is_some_and(|now| store.is_fresh(now, config)),
+                })
+            })
+            .collect();
+        json!({
+            "enabled": config.entries_ttl_ms > 0,
+            "ttl_ms": config.entries_ttl_ms,
+            "max_uses": config.entries_max_uses,
+            "max_bytes": config.entries_max_bytes,
+            "hits": stats.hits,
+            "misses": stats.misses,
+            "bypassed": stats.bypassed,
+            "hit_rate": (lookups > 0).then(|| stats.hits as f64 / lookups as f64),
+            "invalidations": stats.invalidations,
+            "stores": stores,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> CacheConfig {
+        CacheConfig {
+            entries_ttl_ms: 1000,
+            entries_max_uses: 3,
+            entries_max_bytes: 64,
+            ..CacheConfig::default()
+        }
+    }
+
+    fn entries() -> Vec<Entry> {
+        crate::test_support::entries(&[("a", "one"), ("b", "two")])
+    }
+
+    #[test]
+    fn serves_fresh_copy_and_expires_after_ttl() {
+        let config = config();
+        let mut cache = EntryCache::default();
+        cache.fill("s", &entries(), Some(10_000), &config);
+
+        assert_eq!(cache.entries("s", Some(10_500), &config).unwrap().len(), 2);
+        assert_eq!(
+            cache.entry("s", "a", Some(10_999), &config),
+            Some(Some(b"one".to_vec()))
+        );
+        assert_eq!(cache.entry("s", "zz", Some(10_999), &config), Some(None));
+        assert!(cache.entries("s", Some(11_000), &config).is_none());
+        assert_eq!(cache.stats.hits, 3);
+        assert_eq!(cache.stats.misses, 1);
+    }
+
+    #[test]
+    fn bypasses_without_client_time() {
+        let config = config();
+        let mut cache = EntryCache::default();
+        cache.fill("s", &entries(), None, &config);
+        assert!(cache.stores.is_empty());
+
+        cache.fill("s", &entries(), Some(10_000), &config);
+        assert!(cache.entries("s", None, &config).is_none());
+        assert_eq!(cache.stats.bypassed, 1);
+        assert_eq!(cache.stats.hits, 0);
+    }
+
+    #[test]
+    fn listings_without_time_keep_the_copy_alive_but_not_longer() {
+        let config = config();
+        let mut cache = EntryCache::default();
+        cache.fill("s", &entries(), Some(10_000), &config);
+        assert!(cache.entries("s", Some(10_100), &config).is_some());
+
+        let newer = crate::test_support::entries(&[("a", "uno")]);
+        cache.fill("s", &newer, None, &config);
+        let listed = cache.entries("s", Some(10_200), &config).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].value, b"uno");
+        assert_eq!(cache.stores["s"].loaded_at, 10_000);
+        assert_eq!(cache.stores["s"].uses, 2);
+        assert!(cache.entries("s", Some(11_000), &config).is_none());
+    }
+
+    #[test]
+    fn skewed_clocks_only_cause_misses() {
+        let config = config();
+        let mut cache = EntryCache::default();
+
+        // A clock far ahead fills the cache; an honest clock is behind it.
+        cache.fill("s", &entries(), Some(u64::MAX - 10), &config);
+        assert!(cache.entries("s", Some(10_000), &config).is_none());
+
+        // An honest fill is not kept alive by a later, far-ahead request.
+        cache.fill("s", &entries(), Some(10_000), &config);
+        assert!(cache.entries("s", Some(u64::MAX), &config).is_none());
+        assert!(cache.entries("s", Some(10_100), &config).is_some());
+    }
+
+    #[test]
+    fn limits_uses_of_a_copy() {
+        let config = config();
+        let mut cache = EntryCache::default();
+        cache.fill("s", &entries(), Some(10_000), &config);
+        // A constant client time never ages the copy; the use limit does.
+        for _ in 0..3 {
+            assert!(cache.entries("s", Some(10_000), &config).is_some());
+        }
+        assert!(cache.entries("s", Some(10_000), &config).is_none());
+    }
+
+    #[test]
+    fn skips_stores_over_the_size_limit() {
+        let config = CacheConfig {
+            entries_max_bytes: 5,
+            ..config()
+        };
+        let mut cache = EntryCache::default();
+        cache.fill("s", &entries(), Some(10_000), &config);
+        assert!(cache.stores.is_empty());
+    }
+
+    #[test]
+    fn disabled_with_zero_ttl() {
+        let config = CacheConfig {
+            entries_ttl_ms: 0,
+            ..config()
+        };
+        let mut cache = EntryCache::default();
+        cache.fill("s", &entries(), Some(10_000), &config);
+        assert!(cache.entries("s", Some(10_000), &config).is_none());
+        assert_eq!(cache.stats.misses + cache.stats.bypassed, 0);
+    }
+
+    #[test]
+    fn updates_and_invalidation() {
+        let config = config();
+        let mut cache = EntryCache::default();
+        cache.fill("s", &entries(), Some(10_000), &config);
+
+        cache.update("s", "a", Some(b"uno!"));
+        cache.update("s", "b", None);
+        assert_eq!(cache.stores["s"].size, 4);
+        let listed = cache.entries("s", Some(10_001), &config).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].value, b"uno!");
+
+        cache.invalidate("s");
+        cache.invalidate("s");
+        assert!(cache.entries("s", Some(10_002), &config).is_none());
+        assert_eq!(cache.stats.invalidations, 1);
+    }
+}
@@ -0,0 +1,164 @@
+//! ETag / If-None-Match handling and Cache-Control policies.
+
+use crate::bindings::exports::ntwk::theater::http_server::HttpResponse;
+use crate::router::RequestContext;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+
+/// `Cache-Control` values applied to each class of response, and how long
+/// the actor keeps its own copy of a store's entries (see `cache.rs`).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct CacheConfig {
+    pub assets: String,
+    pub store_data: String,
+    /// 0 disables the entry cache.
+    pub entries_ttl_ms: u64,
+    /// Lookups a cached listing serves before it is reloaded.
+    pub entries_max_uses: u64,
+    /// Stores whose values add up to more than this are not cached.
+    pub entries_max_bytes: usize,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        CacheConfig {
+            assets: "public, max-age=60".to_string(),
+            store_data: "no-cache".to_string(),
+            entries_ttl_ms: 5000,
+            entries_max_uses: 100,
+            entries_max_bytes: 16 * 1024 * 1024,
+        }
+    }
+}
+
+/// Strong ETag for a response body.
+pub fn etag(body: &[u8]) -> String {
+    format!("\"{:x}\"", Sha1::digest(body))
+}
+
+/// Whether an `If-None-Match` header value matches `etag`. Weak validators
+/// compare equal to their strong counterpart, as RFC 9110 requires for GET.
+fn matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match
+        .split(',')
+        .map(|tag| tag.trim())
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
+/// Adds `ETag` and `Cache-Control` to a successful response and turns it into
+/// a `304 Not Modified` if the client already holds the same representation.
+pub fn conditional(
+    if_none_match: Option<&str>,
+    mut response: HttpResponse,
+    cache_control: &str,
+) -> HttpResponse {
+    if response.status != 200 {
+        return response;
+    }
+    let tag = etag(response.body.as_deref().unwrap_or_default());
+    response.headers.push(("ETag".to_string(), tag.clone()));
+    if !cache_control.is_empty() {
+        response
+            .headers
+            .push(("Cache-Control".to_string(), cache_control.to_string()));
+    }
+
+    if if_none_match.is_some_and(|value| matches(value, &tag)) {
+        response.status = 304;
+        response.body = None;
+        response
+            .headers
+            .retain(|(name, _)| !name.eq_ignore_ascii_case("Content-Type"));
+    }
+    response
+}
+
+/// [`conditional`] for a routed request.
+pub fn revalidate(
+    req: &RequestContext,
+    response: HttpResponse,
+    cache_control: &str,
+) -> HttpResponse {
+    conditional(req.header("If-None-Match"), response, cache_control)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ok(body: &str) -> HttpResponse {
+        HttpResponse {
+            status: 200,
+            headers: vec![("Content-Type".to_string(), "text/plain".to_string())],
+            body: Some(body.as_bytes().to_vec()),
+        }
+    }
+
+    fn headers(response: &HttpResponse) -> Vec<(&str, &str)> {
+        response
+            .headers
+            .iter()
+            .map(|(n, v)| (n.as_str(), v.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn matches_strong_weak_any_and_lists() {
+        let tag = "\"abc\"";
+        assert!(matches("\"abc\"", tag));
+        assert!(matches("W/\"abc\"", tag));
+        assert!(matches("*", tag));
+        assert!(matches("\"x\", W/\"abc\" ,\"y\"", tag));
+        assert!(!matches("\"abcd\"", tag));
+        assert!(!matches("abc", tag));
+        assert!(!matches("\"x\", \"y\"", tag));
+        assert!(!matches("", tag));
+    }
+
+    #[test]
+    fn adds_validators_to_fresh_responses() {
+        let response = conditional(None, ok("hello"), "no-cache");
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body.as_deref(), Some(&b"hello"[..]));
+        let tag = etag(b"hello");
+        assert_eq!(
+            headers(&response),
+            [
+                ("Content-Type", "text/plain"),
+                ("ETag", tag.as_str()),
+                ("Cache-Control", "no-cache"),
+            ]
+        );
+        let response = conditional(Some("\"stale\""), ok("hello"), "");
+        assert_eq!(response.status, 200);
+        assert_eq!(
+            headers(&response),
+            [("Content-Type", "text/plain"), ("ETag", tag.as_str())]
+        );
+    }
+
+    #[test]
+    fn matching_tags_get_not_modified() {
+        let tag = etag(b"hello");
+        for if_none_match in [tag.clone(), format!("W/{}", tag), "*".to_string()] {
+            let response = conditional(Some(&if_none_match), ok("hello"), "no-cache");
+            assert_eq!(response.status, 304, "{}", if_none_match);
+            assert_eq!(response.body, None);
+            assert_eq!(
+                headers(&response),
+                [("ETag", tag.as_str()), ("Cache-Control", "no-cache")]
+            );
+        }
+    }
+
+    #[test]
+    fn errors_pass_through() {
+        let mut error = ok("gone");
+        error.status = 404;
+        let response = conditional(Some("*"), error, "no-cache");
+        assert_eq!(response.status, 404);
+        assert_eq!(response.body.as_deref(), Some(&b"gone"[..]));
+        assert_eq!(headers(&response), [("Content-Type", "text/plain")]);
+    }
+}
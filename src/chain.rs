@@ -0,0 +1,131 @@
+//! The visualizer's own event chain, from `runtime::get_chain`, for finding
+//! out which request or message changed the actor's state.
+
+use crate::bindings::exports::ntwk::theater::http_server::HttpResponse;
+use crate::bindings::ntwk::theater::runtime::get_chain;
+use crate::bindings::ntwk::theater::types::MetaEvent;
+use crate::router::{ParamError, RequestContext};
+use crate::{encoding, listing, response, State};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+/// Event data larger than this is left out of listings; fetch the single
+/// event to see it.
+const MAX_LISTED_DATA: usize = 64 * 1024;
+
+/// Hashes are `u64`, more than a JavaScript number holds exactly, so they
+/// are sent as fixed-width hex.
+fn hash_hex(hash: u64) -> String {
+    format!("{:016x}", hash)
+}
+
+fn event_json(index: usize, meta: &MetaEvent, include_data: bool) -> Value {
+    let data = &meta.event.data;
+    let mut event = json!({
+        "index": index,
+        "hash": hash_hex(meta.hash),
+        "parent": meta.event.parent.map(hash_hex),
+        "event_type": meta.event.event_type,
+        "size": data.len(),
+    });
+    if include_data {
+        let (encoding, value) = encoding::encode_value(data);
+        event["encoding"] = json!(encoding);
+        event["data"] = value;
+    }
+    event
+}
+
+/// `GET /api/chain?type=&offset=0&limit=100&order=desc&data=true`
+///
+/// Events newest first by default. `type` keeps one event type; `data=false`
+/// leaves out the decoded data, which is also left out for large events.
+pub fn list(req: &RequestContext, state: &mut State) -> HttpResponse {
+    let (offset, limit, include_data) = match (
+        req.query.parse::<usize>("offset"),
+        req.query.parse::<usize>("limit"),
+        req.query.parse::<bool>("data"),
+    ) {
+        (Ok(offset), Ok(limit), Ok(data)) => (
+            offset.unwrap_or(0),
+            limit
+                .unwrap_or(state.config.page_size)
+                .clamp(1, listing::MAX_PAGE_SIZE),
+            data.unwrap_or(true),
+        ),
+        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => return e.into_response(),
+    };
+    let newest_first = match req.query.get("order").unwrap_or("desc") {
+        "asc" => false,
+        "desc" => true,
+        _ => {
+            return ParamError {
+                name: "order".to_string(),
+                message: "expected asc or desc".to_string(),
+            }
+            .into_response()
+        }
+    };
+    let event_type = req.query.get("type").filter(|t| !t.is_empty());
+
+    let chain = get_chain();
+    let mut types: BTreeMap<&str, usize> = BTreeMap::new();
+    for meta in &chain.events {
+        *types.entry(meta.event.event_type.as_str()).or_default() += 1;
+    }
+
+    let mut selected: Vec<(usize, &MetaEvent)> = chain
+        .events
+        .iter()
+        .enumerate()
+        .filter(|(_, meta)| event_type.is_none_or(|t| meta.event.event_type == t))
+        .collect();
+    if newest_first {
+        selected.reverse();
+    }
+    let total = selected.len();
+    let events: Vec<Value> = selected
+        .into_iter()
+        .skip(offset)
+        .take(limit)
+        .map(|(index, meta)| {
+            let fits = meta.event.data.len() <= MAX_LISTED_DATA;
+            let mut event = event_json(index, meta, include_data && fits);
+            if include_data && !fits {
+                event["truncated"] = json!(true);
+            }
+            event
+        })
+        .collect();
+    let end = offset.saturating_add(limit);
+
+    response::success(json!({
+        "events": events,
+        "total": total,
+        "length": chain.events.len(),
+        "types": types,
+        "next_offset": (end < total).then_some(end),
+    }))
+}
+
+/// `GET /api/chain/:hash`: one event with its full data.
+pub fn get(req: &RequestContext, _: &mut State) -> HttpResponse {
+    let hash = req.param("hash");
+    let Ok(wanted) = u64::from_str_radix(hash, 16) else {
+        return response::error_with_code(
+            400,
+            "invalid_parameter",
+            &format!("Invalid event hash '{}': expected hex", hash),
+        );
+    };
+    let chain = get_chain();
+    match chain
+        .events
+        .iter()
+        .enumerate()
+        .find(|(_, meta)| meta.hash == wanted)
+    {
+        Some((index, meta)) => response::success(json!({ "event": event_json(index, meta, true) })),
+        None => response::error(404, &format!("No event with hash {}", hash)),
+    }
+}
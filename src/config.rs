@@ -0,0 +1,270 @@
+//! Actor configuration, read from the init data (`assets/init.json`).
+
+use crate::assets::AssetConfig;
+use crate::auth::AuthConfig;
+use crate::caching::CacheConfig;
+use crate::dump;
+use crate::listing::{DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE};
+use crate::live::LiveConfig;
+use crate::snapshots::SnapshotConfig;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+/// Name under which the top-level `store_id` is registered.
+pub const DEFAULT_STORE_NAME: &str = "default";
+
+/// Presentation options passed through to the UI.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct DisplayConfig {
+    pub title: String,
+    /// Characters of each value shown in the entry list.
+    pub preview_length: usize,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        DisplayConfig {
+            title: "Key-Value Store Contents".to_string(),
+            preview_length: 100,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Store actor registered as `default`. Optional when `stores` is set.
+    pub store_id: String,
+    /// Further store actors by name, served under `/api/stores/:name/...`.
+    pub stores: BTreeMap<String, String>,
+    /// Store used by the unprefixed `/api/...` routes. Defaults to `default`
+    /// if `store_id` is set, otherwise to the first of `stores`.
+    pub default_store: Option<String>,
+    pub read_only: bool,
+    pub page_size: usize,
+    pub assets: AssetConfig,
+    pub cache: CacheConfig,
+    pub auth: AuthConfig,
+    pub display: DisplayConfig,
+    pub snapshots: SnapshotConfig,
+    pub live: LiveConfig,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            store_id: String::new(),
+            stores: BTreeMap::new(),
+            default_store: None,
+            read_only: false,
+            page_size: DEFAULT_PAGE_SIZE,
+            assets: AssetConfig::default(),
+            cache: CacheConfig::default(),
+            auth: AuthConfig::default(),
+            display: DisplayConfig::default(),
+            snapshots: SnapshotConfig::default(),
+            live: LiveConfig::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Parses and validates init data. Missing fields take their defaults.
+    pub fn from_json(data: &[u8]) -> Result<Self, String> {
+        let config: Config =
+            serde_json::from_slice(data).map_err(|e| format!("Invalid configuration: {}", e))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks every field and reports all problems at once.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut errors = Vec::new();
+        if self.store_id.trim().is_empty() && self.stores.is_empty() {
+            errors.push("store_id or stores is required".to_string());
+        }
+        for (name, id) in &self.stores {
+            if name.is_empty() || name.contains('/') {
+                errors.push(format!("stores: invalid store name '{}'", name));
+            }
+            if name == DEFAULT_STORE_NAME && !self.store_id.is_empty() {
+                errors.push(format!(
+                    "stores.{} conflicts with store_id; set only one of them",
+                    name
+                ));
+            }
+            if id.trim().is_empty() {
+                errors.push(format!("stores.{} must be a store actor id", name));
+            }
+        }
+        if let Some(name) = &self.default_store {
+            if self.store(name).is_none() {
+                errors.push(format!(
+                    "default_store '{}' is not a configured store",
+                    name
+                ));
+            }
+        }
+        if !(1..=MAX_PAGE_SIZE).contains(&self.page_size) {
+            errors.push(format!("page_size must be between 1 and {}", MAX_PAGE_SIZE));
+        }
+        if !self.assets.prefix.starts_with('/') {
+            errors.push("assets.prefix must start with '/'".to_string());
+        }
+        if self.assets.index_file.is_empty() || self.assets.index_file.contains(['/', '\\']) {
+            errors.push("assets.index_file must be a plain file name".to_string());
+        }
+        if self.assets.root.split(['/', '\\']).any(|s| s == "..") {
+            errors.push("assets.root must not contain '..'".to_string());
+        }
+        if self
+            .auth
+            .token
+            .as_ref()
+            .is_some_and(|t| t.trim().is_empty())
+        {
+            errors.push("auth.token must not be empty when set".to_string());
+        }
+        if dump::check_path(&self.snapshots.dir).is_err() {
+            errors.push("snapshots.dir must be a relative path without '..'".to_string());
+        }
+        if self.live.poll_interval_ms < 100 {
+            errors.push("live.poll_interval_ms must be at least 100".to_string());
+        }
+        if self.display.preview_length == 0 {
+            errors.push("display.preview_length must be greater than 0".to_string());
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Invalid configuration: {}", errors.join("; ")))
+        }
+    }
+
+    /// Every configured store, by name.
+    pub fn registry(&self) -> BTreeMap<String, String> {
+        let mut stores = self.stores.clone();
+        if !self.store_id.is_empty() {
+            stores.insert(DEFAULT_STORE_NAME.to_string(), self.store_id.clone());
+        }
+        stores
+    }
+
+    /// The actor id of the store called `name`.
+    pub fn store(&self, name: &str) -> Option<String> {
+        self.registry().remove(name)
+    }
+
+    /// Name of the store used when a route doesn't name one.
+    pub fn default_store_name(&self) -> String {
+        match &self.default_store {
+            Some(name) => name.clone(),
+            None if !self.store_id.is_empty() => DEFAULT_STORE_NAME.to_string(),
+            None => self.stores.keys().next().cloned().unwrap_or_default(),
+        }
+    }
+
+    /// Applies a JSON merge patch (RFC 7386): objects are merged recursively
+    /// and `null` resets a field to its default. The result is validated as a
+    /// whole, so a rejected patch leaves `self` untouched.
+    pub fn patched(&self, patch: &Value) -> Result<Self, String> {
+        if !patch.is_object() {
+            return Err("Invalid configuration: the patch must be a JSON object".to_string());
+        }
+        let mut value = serde_json::to_value(self).map_err(|e| e.to_string())?;
+        merge(&mut value, patch);
+        let config: Config =
+            serde_json::from_value(value).map_err(|e| format!("Invalid configuration: {}", e))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// The configuration with secrets masked, for logging and the API.
+    pub fn redacted(&self) -> Value {
+        let mut value = serde_json::to_value(self).unwrap_or_default();
+        if self.auth.enabled() {
+            value["auth"]["token"] = json!("<redacted>");
+        }
+        value
+    }
+}
+
+fn merge(target: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = json!({});
+    }
+    let Value::Object(target) = target else {
+        return;
+    };
+    for (key, value) in patch {
+        if value.is_null() {
+            target.remove(key);
+        } else {
+            merge(target.entry(key.clone()).or_insert(Value::Null), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::config;
+
+    #[test]
+    fn merge_follows_rfc_7386() {
+        let mut target = json!({ "a": "b", "c": { "d": "e", "f": "g" }, "list": [1, 2] });
+        merge(
+            &mut target,
+            &json!({ "a": "z", "c": { "f": null, "h": 1 }, "list": [3] }),
+        );
+        assert_eq!(
+            target,
+            json!({ "a": "z", "c": { "d": "e", "h": 1 }, "list": [3] })
+        );
+
+        let mut scalar = json!("x");
+        merge(&mut scalar, &json!({ "a": { "b": null } }));
+        assert_eq!(scalar, json!({ "a": {} }));
+    }
+
+    #[test]
+    fn patched_merges_nested_sections() {
+        let current =
+            config(r#"{"store_id": "kv", "display": {"title": "Old", "preview_length": 20}}"#);
+        let patched = current
+            .patched(&json!({ "display": { "title": "New" }, "stores": { "b": "kv-b" } }))
+            .unwrap();
+        assert_eq!(patched.display.title, "New");
+        assert_eq!(patched.display.preview_length, 20);
+        assert_eq!(patched.store("b").as_deref(), Some("kv-b"));
+        assert_eq!(patched.store("default").as_deref(), Some("kv"));
+    }
+
+    #[test]
+    fn null_resets_to_the_default() {
+        let current = config(r#"{"store_id": "kv", "page_size": 10}"#);
+        let patched = current.patched(&json!({ "page_size": null })).unwrap();
+        assert_eq!(patched.page_size, Config::default().page_size);
+    }
+
+    #[test]
+    fn rejected_patches_report_every_problem() {
+        let current = config(r#"{"store_id": "kv"}"#);
+        let error = current
+            .patched(&json!({ "page_size": 0, "default_store": "nope" }))
+            .unwrap_err();
+        assert!(error.contains("page_size"), "{}", error);
+        assert!(error.contains("default_store 'nope'"), "{}", error);
+
+        assert!(current.patched(&json!({ "unknown": 1 })).is_err());
+        assert!(current.patched(&json!([1])).is_err());
+        assert!(current.patched(&json!({ "store_id": null })).is_err());
+    }
+}
@@ -0,0 +1,191 @@
+//! HTTP handlers backed by the runtime content store (`ntwk:theater/store`).
+//!
+//! These map one-to-one onto the endpoint table in SPEC.md section 4.2.
+
+use crate::bindings::exports::ntwk::theater::http_server::HttpResponse;
+use crate::bindings::ntwk::theater::runtime::log;
+use crate::bindings::ntwk::theater::store::{self, ContentRef};
+use crate::response;
+use crate::router::RequestContext;
+use serde::Deserialize;
+use serde_json::json;
+
+fn content_ref(hash: &str) -> ContentRef {
+    ContentRef {
+        hash: hash.to_string(),
+    }
+}
+
+fn store_error(operation: &str, err: String) -> HttpResponse {
+    log(&format!("Store {} failed: {}", operation, err));
+    response::error_with_code(
+        500,
+        "content_store_error",
+        &format!("Store {} failed: {}", operation, err),
+    )
+}
+
+fn hashes(refs: Vec<ContentRef>) -> Vec<String> {
+    refs.into_iter().map(|r| r.hash).collect()
+}
+
+/// `GET /api/content?offset=&limit=`
+pub fn list_content(req: &RequestContext) -> HttpResponse {
+    let (offset, limit) = match (
+        req.query.parse::<usize>("offset"),
+        req.query.parse::<usize>("limit"),
+    ) {
+        (Ok(offset), Ok(limit)) => (offset.unwrap_or(0), limit),
+        (Err(e), _) | (_, Err(e)) => return e.into_response(),
+    };
+
+    match store::list_all_content() {
+        Ok(refs) => {
+            let total = refs.len();
+            let hashes: Vec<String> = hashes(refs)
+                .into_iter()
+                .skip(offset)
+                .take(limit.unwrap_or(usize::MAX))
+                .collect();
+            response::success(json!({
+                "total": total,
+                "offset": offset,
+                "content": hashes,
+            }))
+        }
+        Err(e) => store_error("list-all-content", e),
+    }
+}
+
+/// `POST /api/content`
+pub fn store_content(req: &RequestContext) -> HttpResponse {
+    match store::store(req.body) {
+        Ok(content_ref) => response::success(json!({ "hash": content_ref.hash })),
+        Err(e) => store_error("store", e),
+    }
+}
+
+/// `GET /api/content/:hash`
+pub fn get_content(req: &RequestContext) -> HttpResponse {
+    let hash = req.param("hash");
+    let content_ref = content_ref(hash);
+    match store::exists(&content_ref) {
+        Ok(true) => {}
+        Ok(false) => return response::error(404, &format!("No content with hash {}", hash)),
+        Err(e) => return store_error("exists", e),
+    }
+    match store::get(&content_ref) {
+        Ok(content) => response::success(json!({
+            "hash": hash,
+            "size": content.len(),
+            "content": content,
+        })),
+        Err(e) => store_error("get", e),
+    }
+}
+
+/// `GET /api/content/:hash/exists`
+pub fn content_exists(req: &RequestContext) -> HttpResponse {
+    let hash = req.param("hash");
+    match store::exists(&content_ref(hash)) {
+        Ok(exists) => response::success(json!({ "hash": hash, "exists": exists })),
+        Err(e) => store_error("exists", e),
+    }
+}
+
+/// `GET /api/stats/size`
+pub fn total_size() -> HttpResponse {
+    match store::calculate_total_size() {
+        Ok(size) => response::success(json!({ "total_size_bytes": size })),
+        Err(e) => store_error("calculate-total-size", e),
+    }
+}
+
+/// `GET /api/labels`
+pub fn list_labels() -> HttpResponse {
+    match store::list_labels() {
+        Ok(labels) => response::success(json!({ "labels": labels })),
+        Err(e) => store_error("list-labels", e),
+    }
+}
+
+/// `GET /api/labels/:name`
+pub fn get_label(req: &RequestContext) -> HttpResponse {
+    let name = req.param("name");
+    match store::get_by_label(name) {
+        Ok(refs) => response::success(json!({
+            "name": name,
+            "content_refs": hashes(refs),
+        })),
+        Err(e) => store_error("get-by-label", e),
+    }
+}
+
+/// `DELETE /api/labels/:name`
+pub fn remove_label(req: &RequestContext) -> HttpResponse {
+    let name = req.param("name");
+    match store::remove_label(name) {
+        Ok(()) => response::success(json!({ "name": name })),
+        Err(e) => store_error("remove-label", e),
+    }
+}
+
+#[derive(Deserialize, Default)]
+struct LabelOptions {
+    #[serde(default)]
+    replace: bool,
+}
+
+/// `PUT /api/labels/:name/content/:hash`
+///
+/// Attaches the label to the content. A `{"replace": true}` body replaces
+/// whatever the label currently points at instead (`replace-at-label`).
+pub fn put_label_content(req: &RequestContext) -> HttpResponse {
+    let (name, hash, body) = (req.param("name"), req.param("hash"), req.body);
+    let options: LabelOptions = if body.is_empty() {
+        LabelOptions::default()
+    } else {
+        match serde_json::from_slice(body) {
+            Ok(options) => options,
+            Err(e) => return response::error(400, &format!("Invalid request body: {}", e)),
+        }
+    };
+
+    let content_ref = content_ref(hash);
+    let result = if options.replace {
+        store::replace_at_label(name, &content_ref).map_err(|e| ("replace-at-label", e))
+    } else {
+        store::label(name, &content_ref).map_err(|e| ("label", e))
+    };
+    match result {
+        Ok(()) => response::success(json!({ "name": name, "hash": hash })),
+        Err((operation, e)) => store_error(operation, e),
+    }
+}
+
+/// `DELETE /api/labels/:name/content/:hash`
+pub fn remove_from_label(req: &RequestContext) -> HttpResponse {
+    let (name, hash) = (req.param("name"), req.param("hash"));
+    match store::remove_from_label(name, &content_ref(hash)) {
+        Ok(()) => response::success(json!({ "name": name, "hash": hash })),
+        Err(e) => store_error("remove-from-label", e),
+    }
+}
+
+/// `POST /api/labels/:name/content`
+pub fn put_at_label(req: &RequestContext) -> HttpResponse {
+    let name = req.param("name");
+    match store::put_at_label(name, req.body) {
+        Ok(content_ref) => response::success(json!({ "name": name, "hash": content_ref.hash })),
+        Err(e) => store_error("put-at-label", e),
+    }
+}
+
+/// `PUT /api/labels/:name/content`
+pub fn replace_content_at_label(req: &RequestContext) -> HttpResponse {
+    let name = req.param("name");
+    match store::replace_content_at_label(name, req.body) {
+        Ok(content_ref) => response::success(json!({ "name": name, "hash": content_ref.hash })),
+        Err(e) => store_error("replace-content-at-label", e),
+    }
+}
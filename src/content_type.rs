@@ -0,0 +1,477 @@
+//! Classifies store values so they can be filtered and rendered by type.
+//!
+//! Formats with a signature (images, compressed data, WASM) are recognised by
+//! their magic bytes. Then come JSON and UTF-8 text, the latter split into
+//! HTML and XML by their opening markup. Binary values without a signature
+//! count as CBOR, MessagePack or protobuf only if they parse as one complete
+//! message of that format.
+
+use serde::de::IgnoredAny;
+
+/// Nesting depth beyond which structured binary is not considered well-formed.
+const MAX_DEPTH: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentType {
+    Json,
+    Text,
+    Html,
+    Xml,
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+    Gzip,
+    Zstd,
+    Wasm,
+    Cbor,
+    MessagePack,
+    Protobuf,
+    Binary,
+}
+
+impl ContentType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ContentType::Json => "json",
+            ContentType::Text => "text",
+            ContentType::Html => "html",
+            ContentType::Xml => "xml",
+            ContentType::Png => "png",
+            ContentType::Jpeg => "jpeg",
+            ContentType::Gif => "gif",
+            ContentType::Webp => "webp",
+            ContentType::Gzip => "gzip",
+            ContentType::Zstd => "zstd",
+            ContentType::Wasm => "wasm",
+            ContentType::Cbor => "cbor",
+            ContentType::MessagePack => "msgpack",
+            ContentType::Protobuf => "protobuf",
+            ContentType::Binary => "binary",
+        }
+    }
+
+    /// Coarse group: `json`, `text`, `image`, `compressed` or `binary`.
+    pub fn category(self) -> &'static str {
+        match self {
+            ContentType::Json => "json",
+            ContentType::Text | ContentType::Html | ContentType::Xml => "text",
+            ContentType::Png | ContentType::Jpeg | ContentType::Gif | ContentType::Webp => "image",
+            ContentType::Gzip | ContentType::Zstd => "compressed",
+            ContentType::Wasm
+            | ContentType::Cbor
+            | ContentType::MessagePack
+            | ContentType::Protobuf
+            | ContentType::Binary => "binary",
+        }
+    }
+
+    pub fn mime(self) -> &'static str {
+        match self {
+            ContentType::Json => "application/json",
+            ContentType::Text => "text/plain; charset=utf-8",
+            ContentType::Html => "text/html; charset=utf-8",
+            ContentType::Xml => "application/xml",
+            ContentType::Png => "image/png",
+            ContentType::Jpeg => "image/jpeg",
+            ContentType::Gif => "image/gif",
+            ContentType::Webp => "image/webp",
+            ContentType::Gzip => "application/gzip",
+            ContentType::Zstd => "application/zstd",
+            ContentType::Wasm => "application/wasm",
+            ContentType::Cbor => "application/cbor",
+            ContentType::MessagePack => "application/vnd.msgpack",
+            ContentType::Protobuf => "application/x-protobuf",
+            ContentType::Binary => "application/octet-stream",
+        }
+    }
+
+    /// Whether a `type` filter value (a type or a category) selects this type.
+    pub fn matches(self, filter: &str) -> bool {
+        filter == self.as_str() || filter == self.category()
+    }
+}
+
+/// Best-effort content type of a value.
+pub fn detect(value: &[u8]) -> ContentType {
+    if let Some(signature) = sniff_signature(value) {
+        return signature;
+    }
+    if serde_json::from_slice::<IgnoredAny>(value).is_ok() {
+        return ContentType::Json;
+    }
+    if let Ok(text) = std::str::from_utf8(value) {
+        return detect_markup(text);
+    }
+    if is_cbor(value) {
+        ContentType::Cbor
+    } else if is_msgpack(value) {
+        ContentType::MessagePack
+    } else if is_protobuf(value) {
+        ContentType::Protobuf
+    } else {
+        ContentType::Binary
+    }
+}
+
+fn sniff_signature(value: &[u8]) -> Option<ContentType> {
+    let detected = if value.starts_with(b"\x89PNG\r\n\x1a\n") {
+        ContentType::Png
+    } else if value.starts_with(&[0xff, 0xd8, 0xff]) {
+        ContentType::Jpeg
+    } else if value.starts_with(b"GIF87a") || value.starts_with(b"GIF89a") {
+        ContentType::Gif
+    } else if value.len() >= 12 && value.starts_with(b"RIFF") && &value[8..12] == b"WEBP" {
+        ContentType::Webp
+    } else if value.starts_with(&[0x1f, 0x8b]) {
+        ContentType::Gzip
+    } else if value.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+        ContentType::Zstd
+    } else if value.starts_with(b"\0asm") {
+        ContentType::Wasm
+    } else if value.starts_with(&[0xd9, 0xd9, 0xf7]) {
+        // CBOR self-describe tag (RFC 8949 section 3.4.6).
+        ContentType::Cbor
+    } else {
+        return None;
+    };
+    Some(detected)
+}
+
+/// Elements common enough in HTML fragments to classify a value by its
+/// first tag.
+const HTML_ELEMENTS: &[&str] = &[
+    "a",
+    "article",
+    "aside",
+    "b",
+    "blockquote",
+    "body",
+    "br",
+    "button",
+    "code",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hr",
+    "i",
+    "iframe",
+    "img",
+    "input",
+    "label",
+    "li",
+    "link",
+    "main",
+    "meta",
+    "nav",
+    "ol",
+    "option",
+    "p",
+    "pre",
+    "script",
+    "section",
+    "select",
+    "small",
+    "span",
+    "strong",
+    "style",
+    "table",
+    "tbody",
+    "td",
+    "textarea",
+    "th",
+    "thead",
+    "title",
+    "tr",
+    "u",
+    "ul",
+];
+
+/// HTML if it starts with a doctype, `<html>` or a common HTML element; XML
+/// if it has an XML declaration or a single root element closed at the end.
+/// Other text that merely starts with `<` is plain text.
+fn detect_markup(text: &str) -> ContentType {
+    let text = text.trim_start_matches('\u{feff}').trim();
+    let start: String = text
+        .chars()
+        .take(256)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    if start.starts_with("<!doctype html") || start.starts_with("<html") {
+        return ContentType::Html;
+    }
+    if start.starts_with("<?xml") || start.starts_with("<!doctype") {
+        return ContentType::Xml;
+    }
+    let Some(rest) = text.strip_prefix('<') else {
+        return ContentType::Text;
+    };
+    let name_len = rest
+        .find(|c: char| !(c.is_alphanumeric() || matches!(c, '-' | '_' | ':' | '.')))
+        .unwrap_or(rest.len());
+    let name = &rest[..name_len];
+    if name.is_empty() || !text.ends_with('>') {
+        return ContentType::Text;
+    }
+    if HTML_ELEMENTS.contains(&name.to_ascii_lowercase().as_str()) {
+        ContentType::Html
+    } else if text.ends_with(&format!("</{}>", name))
+        || (text.ends_with("/>") && text.matches('<').count() == 1)
+    {
+        ContentType::Xml
+    } else {
+        ContentType::Text
+    }
+}
+
+/// Reads a big-endian unsigned integer of `len` bytes at `pos`.
+fn read_uint(data: &[u8], pos: usize, len: usize) -> Option<u64> {
+    let bytes = data.get(pos..pos.checked_add(len)?)?;
+    Some(bytes.iter().fold(0u64, |acc, b| (acc << 8) | u64::from(*b)))
+}
+
+/// Advances `pos` by `len` bytes if that many remain.
+fn skip(data: &[u8], pos: usize, len: u64) -> Option<usize> {
+    let end = pos.checked_add(usize::try_from(len).ok()?)?;
+    (end <= data.len()).then_some(end)
+}
+
+/// A single CBOR data item that spans all of `value`, with an array or map
+/// at the top so that short binary blobs aren't mistaken for numbers.
+fn is_cbor(value: &[u8]) -> bool {
+    matches!(value.first(), Some(b) if matches!(b >> 5, 4 | 5))
+        && cbor_item(value, 0, 0) == Some(value.len())
+}
+
+/// Parses one CBOR item at `pos`, returning the position after it.
+fn cbor_item(data: &[u8], pos: usize, depth: usize) -> Option<usize> {
+    if depth > MAX_DEPTH {
+        return None;
+    }
+    let initial = *data.get(pos)?;
+    let (major, info) = (initial >> 5, initial & 0x1f);
+    let mut pos = pos + 1;
+
+    if info == 31 {
+        // Indefinite length: items (chunks of the same major type for
+        // strings) until a break byte.
+        if !matches!(major, 2..=5) {
+            return None;
+        }
+        loop {
+            if *data.get(pos)? == 0xff {
+                return Some(pos + 1);
+            }
+            if matches!(major, 2 | 3) && data[pos] >> 5 != major {
+                return None;
+            }
+            pos = cbor_item(data, pos, depth + 1)?;
+            if major == 5 {
+                pos = cbor_item(data, pos, depth + 1)?;
+            }
+        }
+    }
+
+    let argument = match info {
+        0..=23 => u64::from(info),
+        24 => read_uint(data, pos, 1)?,
+        25 => read_uint(data, pos, 2)?,
+        26 => read_uint(data, pos, 4)?,
+        27 => read_uint(data, pos, 8)?,
+        _ => return None,
+    };
+    pos += match info {
+        24 => 1,
+        25 => 2,
+        26 => 4,
+        27 => 8,
+        _ => 0,
+    };
+
+    match major {
+        0 | 1 | 7 => Some(pos),
+        2 | 3 => skip(data, pos, argument),
+        4 | 5 => {
+            let items = if major == 5 {
+                argument.checked_mul(2)?
+            } else {
+                argument
+            };
+            // Each item takes at least one byte.
+            if items > (data.len() - pos) as u64 {
+                return None;
+            }
+            for _ in 0..items {
+                pos = cbor_item(data, pos, depth + 1)?;
+            }
+            Some(pos)
+        }
+        6 => cbor_item(data, pos, depth + 1),
+        _ => None,
+    }
+}
+
+/// A single MessagePack value that spans all of `value`, with a map or array
+/// at the top.
+fn is_msgpack(value: &[u8]) -> bool {
+    matches!(value.first(), Some(0x80..=0x9f | 0xdc..=0xdf))
+        && msgpack_item(value, 0, 0) == Some(value.len())
+}
+
+fn msgpack_item(data: &[u8], pos: usize, depth: usize) -> Option<usize> {
+    if depth > MAX_DEPTH {
+        return None;
+    }
+    let marker = *data.get(pos)?;
+    let pos = pos + 1;
+    let (header, payload, items) = match marker {
+        0x00..=0x7f | 0xe0..=0xff | 0xc0 | 0xc2 | 0xc3 => (0, 0, 0),
+        0x80..=0x8f => (0, 0, u64::from(marker & 0x0f) * 2),
+        0x90..=0x9f => (0, 0, u64::from(marker & 0x0f)),
+        0xa0..=0xbf => (0, u64::from(marker & 0x1f), 0),
+        0xc4 | 0xd9 => (1, read_uint(data, pos, 1)?, 0),
+        0xc5 | 0xda => (2, read_uint(data, pos, 2)?, 0),
+        0xc6 | 0xdb => (4, read_uint(data, pos, 4)?, 0),
+        // ext 8/16/32: length, then a type byte, then the data.
+        0xc7 => (2, read_uint(data, pos, 1)?, 0),
+        0xc8 => (3, read_uint(data, pos, 2)?, 0),
+        0xc9 => (5, read_uint(data, pos, 4)?, 0),
+        0xca => (0, 4, 0),
+        0xcb => (0, 8, 0),
+        0xcc | 0xd0 => (0, 1, 0),
+        0xcd | 0xd1 => (0, 2, 0),
+        0xce | 0xd2 => (0, 4, 0),
+        0xcf | 0xd3 => (0, 8, 0),
+        // fixext 1/2/4/8/16: a type byte, then the data.
+        0xd4 => (1, 1, 0),
+        0xd5 => (1, 2, 0),
+        0xd6 => (1, 4, 0),
+        0xd7 => (1, 8, 0),
+        0xd8 => (1, 16, 0),
+        0xdc => (2, 0, read_uint(data, pos, 2)?),
+        0xdd => (4, 0, read_uint(data, pos, 4)?),
+        0xde => (2, 0, read_uint(data, pos, 2)? * 2),
+        0xdf => (4, 0, read_uint(data, pos, 4)? * 2),
+        0xc1 => return None,
+    };
+    let mut pos = skip(data, pos, header)?;
+    pos = skip(data, pos, payload)?;
+    if items > (data.len() - pos) as u64 {
+        return None;
+    }
+    for _ in 0..items {
+        pos = msgpack_item(data, pos, depth + 1)?;
+    }
+    Some(pos)
+}
+
+fn read_varint(data: &[u8], pos: &mut usize) -> Option<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *data.get(*pos)?;
+        *pos += 1;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+/// Whether `value` is a sequence of well-formed protobuf wire-format fields.
+/// Without the schema this can only be a plausibility check.
+fn is_protobuf(value: &[u8]) -> bool {
+    let mut pos = 0;
+    while pos < value.len() {
+        let Some(tag) = read_varint(value, &mut pos) else {
+            return false;
+        };
+        if tag >> 3 == 0 || tag >> 3 > 0x1fff_ffff {
+            return false;
+        }
+        let next = match tag & 7 {
+            0 => read_varint(value, &mut pos).map(|_| pos),
+            1 => skip(value, pos, 8),
+            2 => read_varint(value, &mut pos).and_then(|len| skip(value, pos, len)),
+            5 => skip(value, pos, 4),
+            _ => None,
+        };
+        match next {
+            Some(next) => pos = next,
+            None => return false,
+        }
+    }
+    !value.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_signatures() {
+        assert_eq!(detect(b"\x89PNG\r\n\x1a\nrest"), ContentType::Png);
+        assert_eq!(detect(&[0xff, 0xd8, 0xff, 0xe0]), ContentType::Jpeg);
+        assert_eq!(detect(b"RIFF\0\0\0\0WEBPVP8 "), ContentType::Webp);
+        assert_eq!(detect(&[0x1f, 0x8b, 8, 0]), ContentType::Gzip);
+        assert_eq!(detect(b"\0asm\x01\0\0\0"), ContentType::Wasm);
+    }
+
+    #[test]
+    fn detects_json_and_text() {
+        assert_eq!(detect(br#"{"a": [1, 2]}"#), ContentType::Json);
+        assert_eq!(detect(b"42"), ContentType::Json);
+        assert_eq!(detect(b"hello world"), ContentType::Text);
+        assert_eq!(detect(b""), ContentType::Text);
+    }
+
+    #[test]
+    fn tells_html_from_xml() {
+        assert_eq!(detect(b"<!DOCTYPE html><p>x</p>"), ContentType::Html);
+        assert_eq!(detect(b"<p>hi</p>"), ContentType::Html);
+        assert_eq!(detect(b"<div class=\"x\">a</div>"), ContentType::Html);
+        assert_eq!(detect(b"<br/>"), ContentType::Html);
+        assert_eq!(detect(b"<?xml version=\"1.0\"?><note/>"), ContentType::Xml);
+        assert_eq!(detect(b"<note><to>A</to></note>"), ContentType::Xml);
+        assert_eq!(detect(b"<ns:item id=\"1\"/>"), ContentType::Xml);
+    }
+
+    #[test]
+    fn unclear_markup_is_text() {
+        assert_eq!(detect(b"<foo>bar</baz>"), ContentType::Text);
+        assert_eq!(detect(b"<3 you"), ContentType::Text);
+        assert_eq!(detect(b"< not a tag >"), ContentType::Text);
+    }
+
+    #[test]
+    fn structured_binary_must_parse_completely() {
+        // CBOR {"a": 1} and MessagePack {"a": -1}; a map missing its value
+        // is neither.
+        assert_eq!(detect(&[0xa1, 0x61, b'a', 0x01]), ContentType::Cbor);
+        assert_eq!(detect(&[0x81, 0xa1, b'a', 0xff]), ContentType::MessagePack);
+        assert_eq!(detect(&[0xa1, 0x61, b'a']), ContentType::Binary);
+        // Protobuf field 1, varint 150.
+        assert_eq!(detect(&[0x08, 0x96, 0x01]), ContentType::Protobuf);
+        assert_eq!(detect(&[0xff, 0xff, 0xff]), ContentType::Binary);
+    }
+
+    #[test]
+    fn filters_by_type_or_category() {
+        assert!(ContentType::Html.matches("html"));
+        assert!(ContentType::Html.matches("text"));
+        assert!(!ContentType::Html.matches("xml"));
+        assert_eq!(ContentType::Webp.category(), "image");
+    }
+}
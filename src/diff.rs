@@ -0,0 +1,228 @@
+//! Key-by-key comparison of two sets of store entries.
+
+use crate::listing::Entry;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    /// Only on the right-hand side.
+    Added,
+    /// Only on the left-hand side.
+    Removed,
+    Changed,
+    Identical,
+}
+
+/// One difference inside a changed JSON value, addressed by JSON Pointer.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Change {
+    pub path: String,
+    pub op: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub left: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub right: Option<Value>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct KeyDiff {
+    pub key: String,
+    pub status: Status,
+    pub left_size: Option<usize>,
+    pub right_size: Option<usize>,
+    /// Structured changes when both sides of a changed key are JSON.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changes: Option<Vec<Change>>,
+}
+
+/// The value of a key on the left and right side, if present.
+type Sides = (Option<Vec<u8>>, Option<Vec<u8>>);
+
+/// Classifies every key of `left` and `right`, in key order.
+pub fn compare(left: Vec<Entry>, right: Vec<Entry>) -> Vec<KeyDiff> {
+    let mut sides: BTreeMap<String, Sides> = BTreeMap::new();
+    for entry in left {
+        sides.entry(entry.key).or_default().0 = Some(entry.value);
+    }
+    for entry in right {
+        sides.entry(entry.key).or_default().1 = Some(entry.value);
+    }
+
+    sides
+        .into_iter()
+        .map(|(key, (left, right))| {
+            let status = match (&left, &right) {
+                (None, _) => Status::Added,
+                (_, None) => Status::Removed,
+                (Some(l), Some(r)) if l == r => Status::Identical,
+                _ => Status::Changed,
+            };
+            let changes = match (status, &left, &right) {
+                (Status::Changed, Some(l), Some(r)) => json_changes(l, r),
+                _ => None,
+            };
+            KeyDiff {
+                key,
+                status,
+                left_size: left.as_ref().map(Vec::len),
+                right_size: right.as_ref().map(Vec::len),
+                changes,
+            }
+        })
+        .collect()
+}
+
+fn json_changes(left: &[u8], right: &[u8]) -> Option<Vec<Change>> {
+    let left: Value = serde_json::from_slice(left).ok()?;
+    let right: Value = serde_json::from_slice(right).ok()?;
+    let mut changes = Vec::new();
+    diff_values("", &left, &right, &mut changes);
+    Some(changes)
+}
+
+/// Escapes a token for use in a JSON Pointer (RFC 6901).
+fn pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+fn diff_values(path: &str, left: &Value, right: &Value, out: &mut Vec<Change>) {
+    match (left, right) {
+        (Value::Object(l), Value::Object(r)) => {
+            for (key, lv) in l {
+                let child = format!("{}/{}", path, pointer_token(key));
+                match r.get(key) {
+                    Some(rv) => diff_values(&child, lv, rv, out),
+                    None => out.push(Change {
+                        path: child,
+                        op: "remove",
+                        left: Some(lv.clone()),
+                        right: None,
+                    }),
+                }
+            }
+            for (key, rv) in r {
+                if !l.contains_key(key) {
+                    out.push(Change {
+                        path: format!("{}/{}", path, pointer_token(key)),
+                        op: "add",
+                        left: None,
+                        right: Some(rv.clone()),
+                    });
+                }
+            }
+        }
+        (Value::Array(l), Value::Array(r)) => {
+            for i in 0..l.len().max(r.len()) {
+                let child = format!("{}/{}", path, i);
+                match (l.get(i), r.get(i)) {
+                    (Some(lv), Some(rv)) => diff_values(&child, lv, rv, out),
+                    (Some(lv), None) => out.push(Change {
+                        path: child,
+                        op: "remove",
+                        left: Some(lv.clone()),
+                        right: None,
+                    }),
+                    (None, Some(rv)) => out.push(Change {
+                        path: child,
+                        op: "add",
+                        left: None,
+                        right: Some(rv.clone()),
+                    }),
+                    (None, None) => {}
+                }
+            }
+        }
+        _ if left != right => out.push(Change {
+            path: path.to_string(),
+            op: "replace",
+            left: Some(left.clone()),
+            right: Some(right.clone()),
+        }),
+        _ => {}
+    }
+}
+
+/// Per-status key counts.
+pub fn summary(diffs: &[KeyDiff]) -> Value {
+    let count = |status| diffs.iter().filter(|d| d.status == status).count();
+    json!({
+        "added": count(Status::Added),
+        "removed": count(Status::Removed),
+        "changed": count(Status::Changed),
+        "identical": count(Status::Identical),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::entries;
+
+    #[test]
+    fn classifies_keys_in_order() {
+        let diffs = compare(
+            entries(&[("b", "same"), ("c", "old"), ("a", "gone")]),
+            entries(&[("d", "new"), ("b", "same"), ("c", "newer")]),
+        );
+        let statuses: Vec<(&str, Status)> =
+            diffs.iter().map(|d| (d.key.as_str(), d.status)).collect();
+        assert_eq!(
+            statuses,
+            [
+                ("a", Status::Removed),
+                ("b", Status::Identical),
+                ("c", Status::Changed),
+                ("d", Status::Added),
+            ]
+        );
+        assert_eq!(
+            (diffs[2].left_size, diffs[2].right_size),
+            (Some(3), Some(5))
+        );
+        assert_eq!((diffs[0].left_size, diffs[0].right_size), (Some(4), None));
+        // Non-JSON values carry no structured changes.
+        assert!(diffs[2].changes.is_none());
+        assert_eq!(
+            summary(&diffs),
+            json!({ "added": 1, "removed": 1, "changed": 1, "identical": 1 })
+        );
+    }
+
+    #[test]
+    fn reports_json_changes_by_pointer() {
+        let diffs = compare(
+            entries(&[(
+                "k",
+                r#"{"a": 1, "gone": true, "list": [1, 2], "a/b~": "x"}"#,
+            )]),
+            entries(&[("k", r#"{"a": 2, "new": null, "list": [1], "a/b~": "x"}"#)]),
+        );
+        let changes = diffs[0].changes.as_ref().unwrap();
+        let ops: Vec<(&str, &str)> = changes.iter().map(|c| (c.path.as_str(), c.op)).collect();
+        assert_eq!(
+            ops,
+            [
+                ("/a", "replace"),
+                ("/gone", "remove"),
+                ("/list/1", "remove"),
+                ("/new", "add"),
+            ]
+        );
+        assert_eq!(changes[0].left, Some(json!(1)));
+        assert_eq!(changes[0].right, Some(json!(2)));
+        assert_eq!(changes[3].right, Some(Value::Null));
+    }
+
+    #[test]
+    fn escapes_pointer_tokens() {
+        assert_eq!(pointer_token("a/b~c"), "a~1b~0c");
+        let diffs = compare(
+            entries(&[("k", r#"{"a/b": 1}"#)]),
+            entries(&[("k", r#"{"a/b": 2}"#)]),
+        );
+        assert_eq!(diffs[0].changes.as_ref().unwrap()[0].path, "/a~1b");
+    }
+}
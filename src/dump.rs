@@ -0,0 +1,74 @@
+//! JSON dumps of store entries kept in the `filesystem` handler.
+//!
+//! A dump is either an array of entries (the shape of
+//! `/api/store-contents`' `entries`) or an object with an `entries` array,
+//! which lets snapshot files carry metadata next to the data.
+
+use crate::bindings::exports::ntwk::theater::http_server::HttpResponse;
+use crate::bindings::ntwk::theater::filesystem::read_file;
+use crate::listing::Entry;
+use crate::response;
+use serde::Deserialize;
+use std::fmt;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DumpFile {
+    Entries(Vec<Entry>),
+    Wrapped { entries: Vec<Entry> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DumpError {
+    /// The path tries to leave the filesystem handler's root.
+    InvalidPath(String),
+    /// `read_file` failed.
+    Unreadable(String),
+    /// The file is not a dump.
+    Invalid(String),
+}
+
+impl DumpError {
+    pub fn into_response(self) -> HttpResponse {
+        let (status, code) = match &self {
+            DumpError::InvalidPath(_) => (400, "invalid_path"),
+            DumpError::Unreadable(_) => (404, "dump_unreadable"),
+            DumpError::Invalid(_) => (422, "invalid_dump"),
+        };
+        response::error_with_code(status, code, &self.to_string())
+    }
+}
+
+impl fmt::Display for DumpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DumpError::InvalidPath(path) => write!(f, "Invalid dump path '{}'", path),
+            DumpError::Unreadable(e) => write!(f, "Could not read the dump: {}", e),
+            DumpError::Invalid(e) => write!(f, "Not a valid dump: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for DumpError {}
+
+/// Rejects absolute paths and `..` so dumps stay inside the handler's root.
+pub fn check_path(path: &str) -> Result<(), DumpError> {
+    let escapes = path.starts_with('/') || path.split(['/', '\\']).any(|s| s == "..");
+    if path.is_empty() || escapes || path.contains('\0') {
+        return Err(DumpError::InvalidPath(path.to_string()));
+    }
+    Ok(())
+}
+
+pub fn parse(data: &[u8]) -> Result<Vec<Entry>, DumpError> {
+    match serde_json::from_slice(data) {
+        Ok(DumpFile::Entries(entries)) | Ok(DumpFile::Wrapped { entries }) => Ok(entries),
+        Err(e) => Err(DumpError::Invalid(e.to_string())),
+    }
+}
+
+pub fn read(path: &str) -> Result<Vec<Entry>, DumpError> {
+    check_path(path)?;
+    let data = read_file(path).map_err(|e| DumpError::Unreadable(format!("{}: {}", path, e)))?;
+    parse(&data)
+}
@@ -0,0 +1,58 @@
+//! Text encodings for binary values.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn to_base64(bytes: &[u8]) -> String {
+    STANDARD.encode(bytes)
+}
+
+/// How a value is represented in exports and imports.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ValueEncoding {
+    /// The value is embedded as a JSON document.
+    Json,
+    /// The value is a UTF-8 string.
+    Text,
+    Base64,
+}
+
+impl ValueEncoding {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ValueEncoding::Json => "json",
+            ValueEncoding::Text => "text",
+            ValueEncoding::Base64 => "base64",
+        }
+    }
+}
+
+/// Picks the most readable encoding for `bytes` and applies it.
+pub fn encode_value(bytes: &[u8]) -> (ValueEncoding, Value) {
+    if let Ok(value) = serde_json::from_slice::<Value>(bytes) {
+        return (ValueEncoding::Json, value);
+    }
+    match std::str::from_utf8(bytes) {
+        Ok(text) => (ValueEncoding::Text, Value::String(text.to_string())),
+        Err(_) => (ValueEncoding::Base64, Value::String(to_base64(bytes))),
+    }
+}
+
+/// Turns an encoded value back into the bytes to store.
+pub fn decode_value(encoding: ValueEncoding, value: &Value) -> Result<Vec<u8>, String> {
+    match (encoding, value) {
+        (ValueEncoding::Json, value) => serde_json::to_vec(value).map_err(|e| e.to_string()),
+        (ValueEncoding::Text, Value::String(text)) => Ok(text.as_bytes().to_vec()),
+        (ValueEncoding::Base64, Value::String(data)) => STANDARD
+            .decode(data)
+            .map_err(|e| format!("invalid base64: {}", e)),
+        (encoding, _) => Err(format!("a {} value must be a string", encoding.as_str())),
+    }
+}
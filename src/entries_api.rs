@@ -0,0 +1,271 @@
+//! HTTP handlers for the key-value store actor's entries.
+
+use crate::bindings::exports::ntwk::theater::http_server::HttpResponse;
+use crate::bindings::ntwk::theater::runtime::log;
+use crate::content_type::ContentType;
+use crate::listing::{self, ListOptions};
+use crate::query::Query;
+use crate::router::RequestContext;
+use crate::search::SearchFilter;
+use crate::{caching, content_type, encoding, hexdump, response, State};
+use serde_json::{json, Value};
+
+fn filtered_page(req: &RequestContext, state: &State, filter: &SearchFilter) -> HttpResponse {
+    let options = match ListOptions::from_query(&req.query, state.config.page_size) {
+        Ok(options) => options,
+        Err(e) => return e.into_response(),
+    };
+    match state.get_all_entries(req.store_id) {
+        Ok(entries) => {
+            log(&format!("Loaded {} entries", entries.len()));
+            let page = listing::paginate(filter.apply(entries), &options);
+            caching::revalidate(
+                req,
+                response::success(page.to_json(state.config.display.preview_length)),
+                &state.config.cache.store_data,
+            )
+        }
+        Err(e) => {
+            log(&format!("Failed to load store contents: {}", e));
+            e.into_response()
+        }
+    }
+}
+
+/// `GET /api/store-contents`
+pub fn list(req: &RequestContext, state: &mut State) -> HttpResponse {
+    filtered_page(req, state, &SearchFilter::default())
+}
+
+/// `GET /api/search`
+pub fn search(req: &RequestContext, state: &mut State) -> HttpResponse {
+    match SearchFilter::from_query(&req.query) {
+        Ok(filter) => filtered_page(req, state, &filter),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// `GET /api/query?q=<expression>`
+///
+/// Evaluates a JSONPath or jq-style expression against every JSON-valued
+/// entry (narrowed by the usual search filters) and returns the keys that
+/// produced at least one value, together with those values.
+pub fn query(req: &RequestContext, state: &mut State) -> HttpResponse {
+    let Some(expression) = req.query.get("q") else {
+        return response::error(400, "Missing query expression 'q'");
+    };
+    let query = match Query::parse(expression) {
+        Ok(query) => query,
+        Err(e) => return response::error(400, &format!("Invalid query expression: {}", e)),
+    };
+    let (filter, offset, limit) = match (
+        SearchFilter::from_query(&req.query),
+        req.query.parse::<usize>("offset"),
+        req.query.parse::<usize>("limit"),
+    ) {
+        (Ok(filter), Ok(offset), Ok(limit)) => (
+            filter,
+            offset.unwrap_or(0),
+            limit
+                .unwrap_or(state.config.page_size)
+                .clamp(1, listing::MAX_PAGE_SIZE),
+        ),
+        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => return e.into_response(),
+    };
+
+    let mut entries = match state.get_all_entries(req.store_id) {
+        Ok(entries) => filter.apply(entries),
+        Err(e) => {
+            log(&format!("Failed to load store contents: {}", e));
+            return e.into_response();
+        }
+    };
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+
+    let mut scanned = 0;
+    let mut results = Vec::new();
+    for entry in &entries {
+        let Ok(value) = serde_json::from_slice::<Value>(&entry.value) else {
+            continue;
+        };
+        scanned += 1;
+        let values = query.evaluate(&value);
+        if !values.is_empty() {
+            results.push(json!({ "key": entry.key, "values": values }));
+        }
+    }
+
+    let total = results.len();
+    let results: Vec<Value> = results.into_iter().skip(offset).take(limit).collect();
+    response::success(json!({
+        "expression": expression,
+        "results": results,
+        "total": total,
+        "scanned": scanned,
+        "skipped": entries.len() - scanned,
+    }))
+}
+
+/// `GET /api/entries/:key?format=raw|json|hex|base64`
+///
+/// Without `format`, JSON values are returned parsed and anything else as
+/// base64.
+pub fn get(req: &RequestContext, state: &mut State) -> HttpResponse {
+    let key = req.param("key");
+    let value = match state.get_entry(req.store_id, key) {
+        Ok(Some(value)) => value,
+        Ok(None) => return response::error(404, &format!("No entry with key {}", key)),
+        Err(e) => {
+            log(&format!("Failed to get entry {}: {}", key, e));
+            return e.into_response();
+        }
+    };
+
+    let content_type = content_type::detect(&value);
+    let format = req
+        .query
+        .get("format")
+        .unwrap_or(if content_type == ContentType::Json {
+            "json"
+        } else {
+            "base64"
+        });
+
+    let encoded = match format {
+        "raw" => {
+            return HttpResponse {
+                status: 200,
+                headers: vec![("Content-Type".to_string(), content_type.mime().to_string())],
+                body: Some(value),
+            };
+        }
+        "json" => match serde_json::from_slice::<Value>(&value) {
+            Ok(parsed) => parsed,
+            Err(e) => return response::error(422, &format!("Value is not JSON: {}", e)),
+        },
+        "hex" => Value::String(encoding::to_hex(&value)),
+        "base64" => Value::String(encoding::to_base64(&value)),
+        other => {
+            return response::error(
+                400,
+                &format!(
+                    "Unknown format '{}', expected raw, json, hex or base64",
+                    other
+                ),
+            )
+        }
+    };
+
+    response::success(json!({
+        "key": key,
+        "size": value.len(),
+        "content_type": content_type.as_str(),
+        "category": content_type.category(),
+        "mime": content_type.mime(),
+        "format": format,
+        "value": encoded,
+        "read_only": state.config.read_only,
+    }))
+}
+
+/// `GET /api/entries/:key/hexdump?offset=0&length=1024&width=16`
+///
+/// One page of the value as hex dump rows, with the offsets of the
+/// neighbouring pages for paging through large values.
+pub fn hexdump(req: &RequestContext, state: &mut State) -> HttpResponse {
+    let (offset, length, width) = match (
+        req.query.parse::<usize>("offset"),
+        req.query.parse::<usize>("length"),
+        req.query.parse::<usize>("width"),
+    ) {
+        (Ok(offset), Ok(length), Ok(width)) => (
+            offset.unwrap_or(0),
+            length
+                .unwrap_or(hexdump::DEFAULT_LENGTH)
+                .clamp(1, hexdump::MAX_LENGTH),
+            width
+                .unwrap_or(hexdump::DEFAULT_WIDTH)
+                .clamp(1, hexdump::MAX_WIDTH),
+        ),
+        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => return e.into_response(),
+    };
+
+    let key = req.param("key");
+    let value = match state.get_entry(req.store_id, key) {
+        Ok(Some(value)) => value,
+        Ok(None) => return response::error(404, &format!("No entry with key {}", key)),
+        Err(e) => return e.into_response(),
+    };
+    if offset > value.len() {
+        return response::error_with_code(
+            400,
+            "invalid_parameter",
+            &format!("offset {} is past the end ({} bytes)", offset, value.len()),
+        );
+    }
+
+    let end = offset.saturating_add(length).min(value.len());
+    let content_type = content_type::detect(&value);
+    response::success(json!({
+        "key": key,
+        "size": value.len(),
+        "content_type": content_type.as_str(),
+        "category": content_type.category(),
+        "mime": content_type.mime(),
+        "offset": offset,
+        "length": end - offset,
+        "width": width,
+        "rows": hexdump::rows(&value[offset..end], offset, width),
+        "next_offset": (end < value.len()).then_some(end),
+        "prev_offset": (offset > 0).then(|| offset.saturating_sub(length)),
+    }))
+}
+
+/// Writes the request body through `Action::Put`. Bodies sent as
+/// `application/json` must parse as JSON.
+fn put(req: &RequestContext, state: &State, previous_key: Option<&str>) -> HttpResponse {
+    let is_json = req
+        .header("Content-Type")
+        .is_some_and(|ct| ct.starts_with("application/json"));
+    if is_json {
+        if let Err(e) = serde_json::from_slice::<Value>(req.body) {
+            return response::error(400, &format!("Invalid JSON: {}", e));
+        }
+    }
+
+    match state.put_value(req.store_id, req.body.to_vec()) {
+        Ok(key) => {
+            log(&format!("Stored {} bytes under {:?}", req.body.len(), key));
+            response::success(json!({
+                "key": key,
+                "previous_key": previous_key,
+                "key_chosen_by_store": true,
+                "key_changed": previous_key.is_some_and(|p| key.as_deref() != Some(p)),
+                "size": req.body.len(),
+            }))
+        }
+        Err(e) => {
+            log(&format!("Put failed: {}", e));
+            e.into_response()
+        }
+    }
+}
+
+/// `POST /api/entries`
+pub fn create(req: &RequestContext, state: &mut State) -> HttpResponse {
+    put(req, state, None)
+}
+
+/// `PUT /api/entries/:key`
+///
+/// The key-value protocol's `Put` carries only the value, so the store actor
+/// decides the key; the response reports it next to the edited entry's key,
+/// which keeps its old value. `:key` must exist.
+pub fn update(req: &RequestContext, state: &mut State) -> HttpResponse {
+    let key = req.param("key");
+    match state.get_entry(req.store_id, key) {
+        Ok(Some(_)) => put(req, state, Some(key)),
+        Ok(None) => response::error(404, &format!("No entry with key {}", key)),
+        Err(e) => e.into_response(),
+    }
+}
@@ -0,0 +1,197 @@
+//! Bulk export of store entries as NDJSON, CSV or JSON.
+
+use crate::bindings::exports::ntwk::theater::http_server::HttpResponse;
+use crate::bindings::ntwk::theater::runtime::log;
+use crate::encoding::{self, ValueEncoding};
+use crate::listing::Entry;
+use crate::router::{ParamError, RequestContext};
+use crate::search::SearchFilter;
+use crate::State;
+use serde_json::{json, Map, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Ndjson,
+    Csv,
+    Json,
+}
+
+impl Format {
+    fn parse(value: &str) -> Result<Self, ParamError> {
+        match value {
+            "ndjson" => Ok(Format::Ndjson),
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            other => Err(ParamError {
+                name: "format".to_string(),
+                message: format!("expected ndjson, csv or json, got '{}'", other),
+            }),
+        }
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            Format::Ndjson => "application/x-ndjson",
+            Format::Csv => "text/csv; charset=utf-8",
+            Format::Json => "application/json",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Ndjson => "ndjson",
+            Format::Csv => "csv",
+            Format::Json => "json",
+        }
+    }
+}
+
+/// `{"key", "encoding", "value"}`, one NDJSON line.
+fn record(entry: &Entry) -> Value {
+    let (encoding, value) = encoding::encode_value(&entry.value);
+    json!({ "key": entry.key, "encoding": encoding, "value": value })
+}
+
+/// Characters that make spreadsheets read a cell as a formula.
+const FORMULA_PREFIXES: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+/// Quotes a CSV field when it contains a separator, quote or line break.
+/// Fields that a spreadsheet would evaluate as a formula get a leading `'`
+/// and are always quoted.
+fn csv_field(field: &str) -> String {
+    if field.starts_with(FORMULA_PREFIXES) {
+        format!("\"'{}\"", field.replace('"', "\"\""))
+    } else if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn render(entries: &[Entry], format: Format) -> Vec<u8> {
+    match format {
+        Format::Ndjson => entries
+            .iter()
+            .map(|entry| format!("{}\n", record(entry)))
+            .collect::<String>()
+            .into_bytes(),
+        Format::Csv => {
+            let mut out = String::from("key,encoding,value\r\n");
+            for entry in entries {
+                let (encoding, value) = encoding::encode_value(&entry.value);
+                let value = match (encoding, value) {
+                    (ValueEncoding::Json, value) => value.to_string(),
+                    (_, Value::String(text)) => text,
+                    (_, value) => value.to_string(),
+                };
+                out.push_str(&format!(
+                    "{},{},{}\r\n",
+                    csv_field(&entry.key),
+                    encoding.as_str(),
+                    csv_field(&value)
+                ));
+            }
+            out.into_bytes()
+        }
+        Format::Json => {
+            let object: Map<String, Value> = entries
+                .iter()
+                .map(|entry| {
+                    let (encoding, value) = encoding::encode_value(&entry.value);
+                    (
+                        entry.key.clone(),
+                        json!({ "encoding": encoding, "value": value }),
+                    )
+                })
+                .collect();
+            serde_json::to_vec_pretty(&Value::Object(object)).unwrap_or_default()
+        }
+    }
+}
+
+/// `GET /api/export?format=ndjson|csv|json` plus the `/api/search` filters.
+pub fn export(req: &RequestContext, state: &mut State) -> HttpResponse {
+    let (format, filter) = match (
+        Format::parse(req.query.get("format").unwrap_or("ndjson")),
+        SearchFilter::from_query(&req.query),
+    ) {
+        (Ok(format), Ok(filter)) => (format, filter),
+        (Err(e), _) | (_, Err(e)) => return e.into_response(),
+    };
+    let mut entries = match state.get_all_entries(req.store_id) {
+        Ok(entries) => filter.apply(entries),
+        Err(e) => return e.into_response(),
+    };
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+    log(&format!(
+        "Exporting {} entries as {}",
+        entries.len(),
+        format.extension()
+    ));
+
+    let filename = format!("store-export.{}", format.extension());
+    HttpResponse {
+        status: 200,
+        headers: vec![
+            (
+                "Content-Type".to_string(),
+                format.content_type().to_string(),
+            ),
+            (
+                "Content-Disposition".to_string(),
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+            (
+                "Cache-Control".to_string(),
+                state.config.cache.store_data.clone(),
+            ),
+        ],
+        body: Some(render(&entries, format)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::entries;
+
+    #[test]
+    fn plain_fields_are_left_alone() {
+        for field in ["", "user:1", "hello world", "3.5", "a'b", "x=1"] {
+            assert_eq!(csv_field(field), field);
+        }
+    }
+
+    #[test]
+    fn separators_quotes_and_line_breaks_are_quoted() {
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("one\ntwo"), "\"one\ntwo\"");
+        assert_eq!(csv_field("one\r\ntwo"), "\"one\r\ntwo\"");
+    }
+
+    #[test]
+    fn formulas_are_neutralized() {
+        assert_eq!(csv_field("=1+1"), "\"'=1+1\"");
+        assert_eq!(csv_field("+1"), "\"'+1\"");
+        assert_eq!(csv_field("-5"), "\"'-5\"");
+        assert_eq!(csv_field("@SUM(A1)"), "\"'@SUM(A1)\"");
+        assert_eq!(csv_field("\t=1"), "\"'\t=1\"");
+        assert_eq!(
+            csv_field("=HYPERLINK(\"http://x\",\"y\")"),
+            "\"'=HYPERLINK(\"\"http://x\"\",\"\"y\"\")\""
+        );
+    }
+
+    #[test]
+    fn renders_csv_rows() {
+        let csv = render(
+            &entries(&[("a,b", "{\"n\": 1}"), ("=cmd", "hello")]),
+            Format::Csv,
+        );
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "key,encoding,value\r\n\"a,b\",json,\"{\"\"n\"\":1}\"\r\n\"'=cmd\",text,hello\r\n"
+        );
+    }
+}
@@ -0,0 +1,98 @@
+//! Offset / hex / ASCII rows for viewing binary values.
+
+use serde::Serialize;
+
+pub const DEFAULT_WIDTH: usize = 16;
+pub const MAX_WIDTH: usize = 64;
+pub const DEFAULT_LENGTH: usize = 1024;
+pub const MAX_LENGTH: usize = 64 * 1024;
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Row {
+    pub offset: usize,
+    /// Space-separated byte values.
+    pub hex: String,
+    /// Printable ASCII, with `.` for everything else.
+    pub ascii: String,
+}
+
+/// Splits `bytes`, which start at `base` within the whole value, into rows of
+/// `width` bytes, clamped to `1..=MAX_WIDTH`.
+pub fn rows(bytes: &[u8], base: usize, width: usize) -> Vec<Row> {
+    let width = width.clamp(1, MAX_WIDTH);
+    bytes
+        .chunks(width)
+        .enumerate()
+        .map(|(i, chunk)| Row {
+            offset: base + i * width,
+            hex: chunk
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect::<Vec<_>>()
+                .join(" "),
+            ascii: chunk
+                .iter()
+                .map(|&b| {
+                    if b.is_ascii_graphic() || b == b' ' {
+                        b as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(offset: usize, hex: &str, ascii: &str) -> Row {
+        Row {
+            offset,
+            hex: hex.to_string(),
+            ascii: ascii.to_string(),
+        }
+    }
+
+    #[test]
+    fn last_row_may_be_partial() {
+        assert_eq!(
+            rows(b"abcdefghij", 0, 4),
+            [
+                row(0, "61 62 63 64", "abcd"),
+                row(4, "65 66 67 68", "efgh"),
+                row(8, "69 6a", "ij"),
+            ]
+        );
+        assert_eq!(rows(b"", 0, 4), []);
+    }
+
+    #[test]
+    fn offsets_start_at_the_base() {
+        assert_eq!(
+            rows(b"\x00\x7f ~\n", 1024, 2),
+            [
+                row(1024, "00 7f", ".."),
+                row(1026, "20 7e", " ~"),
+                row(1028, "0a", ".")
+            ]
+        );
+    }
+
+    #[test]
+    fn width_is_clamped() {
+        assert_eq!(
+            rows(b"abc", 10, 0),
+            [row(10, "61", "a"), row(11, "62", "b"), row(12, "63", "c")]
+        );
+        let wide = rows(&[0u8; 100], 0, 1000);
+        assert_eq!(
+            wide.iter().map(|r| r.offset).collect::<Vec<_>>(),
+            [0, MAX_WIDTH]
+        );
+        assert_eq!(wide[0].ascii.len(), MAX_WIDTH);
+        assert_eq!(wide[1].ascii.len(), 100 - MAX_WIDTH);
+    }
+}
@@ -0,0 +1,316 @@
+//! Bulk import of key/value records through `Action::Put`.
+//!
+//! Accepted bodies: NDJSON with one `{"key", "encoding", "value"}` record per
+//! line, a JSON array of such records, or a JSON object mapping each key to
+//! `{"encoding", "value"}` (the `/api/export?format=json` shape). `encoding`
+//! defaults to `json`.
+//!
+//! Each record is a separate `Put`, which carries only the value: the store
+//! actor picks the key. A record's key only names it in the report, next to
+//! the key the store actually assigned.
+
+use crate::bindings::exports::ntwk::theater::http_server::HttpResponse;
+use crate::bindings::ntwk::theater::runtime::log;
+use crate::encoding::{self, ValueEncoding};
+use crate::protocol::StoreError;
+use crate::router::RequestContext;
+use crate::{response, State};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EncodedValue {
+    #[serde(default = "default_encoding")]
+    encoding: ValueEncoding,
+    value: Value,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Record {
+    key: String,
+    #[serde(default = "default_encoding")]
+    encoding: ValueEncoding,
+    value: Value,
+}
+
+fn default_encoding() -> ValueEncoding {
+    ValueEncoding::Json
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    /// Valid; only reported by dry runs.
+    Valid,
+    /// Written, and the store assigned the record's key.
+    Written,
+    /// Written under `assigned_key`; the record's key is untouched.
+    StoredUnderDifferentKey,
+    /// Written, but the store reported no key.
+    Unconfirmed,
+    Invalid,
+    Failed,
+    /// Not tried because an earlier write lost the store actor.
+    NotAttempted,
+}
+
+#[derive(Serialize, Debug)]
+pub struct KeyReport {
+    pub key: String,
+    /// 1-based NDJSON line or array position of the record.
+    pub record: usize,
+    pub outcome: Outcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<usize>,
+    /// The key the store reported for the write, if it was written and the
+    /// store reported one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assigned_key: Option<String>,
+}
+
+/// A parsed record: its key and either the bytes to write or why not.
+type Parsed = (String, Result<Vec<u8>, String>);
+
+fn decode_record(record: Result<Record, String>) -> Parsed {
+    match record {
+        Ok(record) => {
+            let value = encoding::decode_value(record.encoding, &record.value);
+            (record.key, value)
+        }
+        Err(e) => (String::new(), Err(e)),
+    }
+}
+
+/// Splits a body into records. Fails only if the body as a whole is
+/// unusable; problems with single records are reported per record.
+fn parse(body: &[u8], ndjson: bool) -> Result<Vec<Parsed>, String> {
+    let text = std::str::from_utf8(body).map_err(|e| format!("body is not UTF-8: {}", e))?;
+    if !ndjson {
+        match serde_json::from_str::<Value>(text) {
+            // A one-line NDJSON body is a single record, not a key map.
+            Ok(Value::Object(map)) if !(map.contains_key("key") && map.contains_key("value")) => {
+                return Ok(map
+                    .into_iter()
+                    .map(|(key, value)| {
+                        let value = serde_json::from_value::<EncodedValue>(value)
+                            .map_err(|e| format!("expected {{\"encoding\", \"value\"}}: {}", e))
+                            .and_then(|v| encoding::decode_value(v.encoding, &v.value));
+                        (key, value)
+                    })
+                    .collect())
+            }
+            Ok(Value::Array(items)) => {
+                return Ok(items
+                    .into_iter()
+                    .map(|item| {
+                        decode_record(serde_json::from_value(item).map_err(|e| e.to_string()))
+                    })
+                    .collect())
+            }
+            // Anything else is read as NDJSON sent without its content type.
+            _ => {}
+        }
+    }
+    Ok(text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| decode_record(serde_json::from_str(line).map_err(|e| e.to_string())))
+        .collect())
+}
+
+/// Validates every record. Records need a key to be named in the report;
+/// since the store picks the keys, repeated keys are fine.
+fn validate(records: Vec<Parsed>) -> Vec<(KeyReport, Option<Vec<u8>>)> {
+    records
+        .into_iter()
+        .enumerate()
+        .map(|(i, (key, value))| {
+            let value = value.and_then(|v| match key.as_str() {
+                "" => Err("missing key".to_string()),
+                _ => Ok(v),
+            });
+            let report = KeyReport {
+                key,
+                record: i + 1,
+                outcome: if value.is_ok() {
+                    Outcome::Valid
+                } else {
+                    Outcome::Invalid
+                },
+                error: value.as_ref().err().cloned(),
+                size: value.as_ref().ok().map(Vec::len),
+                assigned_key: None,
+            };
+            (report, value.ok())
+        })
+        .collect()
+}
+
+impl KeyReport {
+    /// Records the key the store reported for the write of this record.
+    fn written(&mut self, assigned: Option<String>) {
+        self.outcome = match assigned.as_deref() {
+            Some(assigned) if assigned == self.key => Outcome::Written,
+            Some(_) => Outcome::StoredUnderDifferentKey,
+            None => Outcome::Unconfirmed,
+        };
+        self.assigned_key = assigned;
+    }
+}
+
+/// `POST /api/import?dry_run=false`
+pub fn import(req: &RequestContext, state: &mut State) -> HttpResponse {
+    let dry_run = match req.query.parse::<bool>("dry_run") {
+        Ok(dry_run) => dry_run.unwrap_or(false),
+        Err(e) => return e.into_response(),
+    };
+    let ndjson = req
+        .header("Content-Type")
+        .is_some_and(|ct| ct.starts_with("application/x-ndjson"));
+    let records = match parse(req.body, ndjson) {
+        Ok(records) => records,
+        Err(e) => return response::error(400, &format!("Invalid import: {}", e)),
+    };
+    if records.is_empty() {
+        return response::error(400, "Invalid import: no records");
+    }
+
+    let mut items = validate(records);
+    if !dry_run {
+        let mut store_lost = false;
+        for (report, value) in items.iter_mut() {
+            let Some(value) = value.take() else {
+                continue;
+            };
+            if store_lost {
+                report.outcome = Outcome::NotAttempted;
+                continue;
+            }
+            match state.put_value(req.store_id, value) {
+                Ok(assigned) => report.written(assigned),
+                Err(e) => {
+                    store_lost = matches!(e, StoreError::Transport(_));
+                    report.outcome = Outcome::Failed;
+                    report.error = Some(e.to_string());
+                }
+            }
+        }
+    }
+
+    let reports: Vec<KeyReport> = items.into_iter().map(|(report, _)| report).collect();
+    let count = |outcome| reports.iter().filter(|r| r.outcome == outcome).count();
+    let summary = json!({
+        "records": reports.len(),
+        "valid": count(Outcome::Valid),
+        "written": count(Outcome::Written),
+        "stored_under_different_key": count(Outcome::StoredUnderDifferentKey),
+        "unconfirmed": count(Outcome::Unconfirmed),
+        "invalid": count(Outcome::Invalid),
+        "failed": count(Outcome::Failed),
+        "not_attempted": count(Outcome::NotAttempted),
+    });
+    log(&format!("Import into {}: {}", req.store_id, summary));
+
+    response::success(json!({
+        "dry_run": dry_run,
+        "summary": summary,
+        "keys": reports,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcomes(body: &str, ndjson: bool) -> Vec<(String, Outcome, Option<String>)> {
+        validate(parse(body.as_bytes(), ndjson).unwrap())
+            .into_iter()
+            .map(|(report, _)| (report.key, report.outcome, report.error))
+            .collect()
+    }
+
+    #[test]
+    fn parses_every_body_shape() {
+        let ndjson = "{\"key\": \"a\", \"value\": {\"n\": 1}}\n\n{\"key\": \"b\", \"encoding\": \"text\", \"value\": \"hi\"}\n";
+        let array =
+            r#"[{"key": "a", "value": 1}, {"key": "b", "encoding": "base64", "value": "aGk="}]"#;
+        let object = r#"{"a": {"value": 1}, "b": {"encoding": "text", "value": "hi"}}"#;
+        for (body, ndjson) in [
+            (ndjson, true),
+            (ndjson, false),
+            (array, false),
+            (object, false),
+        ] {
+            let parsed = validate(parse(body.as_bytes(), ndjson).unwrap());
+            assert_eq!(parsed.len(), 2, "{}", body);
+            assert!(parsed
+                .iter()
+                .all(|(r, v)| r.outcome == Outcome::Valid && v.is_some()));
+            assert_eq!(parsed[1].1.as_deref(), Some(&b"hi"[..]));
+        }
+    }
+
+    #[test]
+    fn repeated_keys_are_valid() {
+        let body = "{\"key\": \"a\", \"value\": 1}\n{\"key\": \"a\", \"value\": 2}";
+        let outcomes = outcomes(body, true);
+        assert!(outcomes
+            .iter()
+            .all(|(_, outcome, _)| *outcome == Outcome::Valid));
+    }
+
+    #[test]
+    fn malformed_records_are_invalid_one_by_one() {
+        let body = [
+            r#"{"key": "ok", "value": 1}"#,
+            r#"{"key": "bad-b64", "encoding": "base64", "value": "@@@"}"#,
+            r#"{"key": "hex", "encoding": "hex", "value": "6869"}"#,
+            r#"{"key": "num", "encoding": "text", "value": 5}"#,
+            r#"{"value": 1}"#,
+            r#"{"key": "", "value": 1}"#,
+            r#"{"key": "trunc", "value": "#,
+        ]
+        .join("\n");
+        let outcomes = outcomes(&body, true);
+        assert_eq!(outcomes[0].1, Outcome::Valid);
+        assert!(outcomes[1..].iter().all(|(_, o, _)| *o == Outcome::Invalid));
+        assert!(outcomes[1].2.as_deref().unwrap().contains("invalid base64"));
+        assert_eq!(
+            outcomes[3].2.as_deref(),
+            Some("a text value must be a string")
+        );
+        assert_eq!(outcomes[5].2.as_deref(), Some("missing key"));
+    }
+
+    #[test]
+    fn csv_is_not_an_import_format() {
+        let outcomes = outcomes("key,encoding,value\na,json,1\n", false);
+        assert_eq!(outcomes.len(), 2);
+        assert!(outcomes.iter().all(|(_, o, _)| *o == Outcome::Invalid));
+        assert!(parse(&[0xff, 0xfe], false).is_err());
+    }
+
+    #[test]
+    fn writes_report_the_assigned_key() {
+        let mut items = validate(parse(br#"[{"key": "a", "value": 1}, {"key": "b", "value": 2}, {"key": "c", "value": 3}]"#, false).unwrap());
+        items[0].0.written(Some("a".to_string()));
+        items[1].0.written(Some("sha-of-2".to_string()));
+        items[2].0.written(None);
+        let reports: Vec<(Outcome, Option<&str>)> = items
+            .iter()
+            .map(|(r, _)| (r.outcome, r.assigned_key.as_deref()))
+            .collect();
+        assert_eq!(
+            reports,
+            [
+                (Outcome::Written, Some("a")),
+                (Outcome::StoredUnderDifferentKey, Some("sha-of-2")),
+                (Outcome::Unconfirmed, None),
+            ]
+        );
+    }
+}
@@ -1,4 +1,35 @@
+mod admin_api;
+mod assets;
+mod auth;
 mod bindings;
+mod cache;
+mod caching;
+mod chain;
+mod config;
+mod content_api;
+mod content_type;
+mod diff;
+mod dump;
+mod encoding;
+mod entries_api;
+mod export;
+mod hexdump;
+mod import;
+mod listing;
+mod live;
+mod messages;
+mod protocol;
+mod query;
+mod response;
+mod restore;
+mod router;
+mod search;
+mod snapshots;
+mod stores_api;
+#[cfg(test)]
+mod test_support;
+mod time;
+mod websocket;
 
 use bindings::exports::ntwk::theater::actor::Guest as ActorGuest;
 use bindings::exports::ntwk::theater::http_server::Guest as HttpGuest;
@@ -6,184 +37,342 @@ use bindings::exports::ntwk::theater::http_server::{
     HttpRequest as ServerHttpRequest, HttpResponse,
 };
 use bindings::exports::ntwk::theater::message_server_client::Guest as MessageServerClientGuest;
-use bindings::ntwk::theater::filesystem::read_file;
-use bindings::ntwk::theater::message_server_host::request;
+use bindings::exports::ntwk::theater::websocket_server::Guest as WebsocketGuest;
+use bindings::exports::ntwk::theater::websocket_server::{WebsocketMessage, WebsocketResponse};
 use bindings::ntwk::theater::runtime::log;
 use bindings::ntwk::theater::types::Json;
+use config::Config;
+use listing::Entry;
+use live::ChangeLog;
+use protocol::StoreError;
+use router::{Dispatch, Params, RequestContext, Router};
 use serde::{Deserialize, Serialize};
-use serde_json::{json, Value};
+use serde_json::json;
 
 #[derive(Serialize, Deserialize, Debug)]
 struct State {
-    store_id: String,
-}
-
-// Reuse the Request/Action types from key-value actor
-#[derive(Serialize, Deserialize, Debug)]
-struct Request {
-    _type: String,
-    data: Action,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-enum Action {
-    Get(String),
-    Put(Vec<u8>),
-    All(()),
+    config: Config,
+    #[serde(default)]
+    changes: ChangeLog,
+    /// Client time of the request being handled, if it sent one. Never
+    /// persisted; it only decides whether the entry cache may be used.
+    #[serde(skip)]
+    clock: Option<u64>,
 }
 
 impl State {
-    fn get_all_entries(&self) -> Result<Value, Box<dyn std::error::Error>> {
-        let req = Request {
-            _type: "request".to_string(),
-            data: Action::All(()),
-        };
+    /// All entries of a store, from the cache while it is fresh.
+    fn get_all_entries(&self, store_id: &str) -> Result<Vec<Entry>, StoreError> {
+        let cached = cache::with(|c| c.entries(store_id, self.clock, &self.config.cache));
+        match cached {
+            Some(entries) => Ok(entries),
+            None => self.reload_entries(store_id),
+        }
+    }
 
-        let request_bytes = serde_json::to_vec(&req)?;
-        let response_bytes = request(&self.store_id, &request_bytes)?;
+    /// All entries of a store, asked from the store actor even if cached.
+    fn reload_entries(&self, store_id: &str) -> Result<Vec<Entry>, StoreError> {
+        let entries = protocol::get_all(store_id)?;
+        cache::with(|c| c.fill(store_id, &entries, self.clock, &self.config.cache));
+        Ok(entries)
+    }
 
-        let response: Value = serde_json::from_slice(&response_bytes)?;
-        if response["status"].as_str() == Some("ok") {
-            Ok(response["data"].clone())
-        } else {
-            Err("Failed to get store entries".into())
+    fn get_entry(&self, store_id: &str, key: &str) -> Result<Option<Vec<u8>>, StoreError> {
+        let cached = cache::with(|c| c.entry(store_id, key, self.clock, &self.config.cache));
+        match cached {
+            Some(value) => Ok(value),
+            None => protocol::get(store_id, key),
         }
     }
-}
 
-#[derive(Serialize, Deserialize, Debug)]
-struct InitData {
-    store_id: String,
+    /// Writes a value. The store's cached copy is dropped whatever the
+    /// outcome, since the store picks the key.
+    fn put_value(&self, store_id: &str, value: Vec<u8>) -> Result<Option<String>, StoreError> {
+        let result = protocol::put(store_id, value);
+        cache::with(|c| c.invalidate(store_id));
+        result
+    }
 }
 
 struct Component;
 
 impl ActorGuest for Component {
-    fn init(data: Option<Vec<u8>>) -> Vec<u8> {
+    fn init(state: Option<Vec<u8>>, params: (String,)) -> Result<(Option<Vec<u8>>,), String> {
         log("Initializing store visualizer actor");
-        log(&format!("Data: {:?}", data));
-        let data = data.unwrap();
-        log(&format!(
-            "Data: {}",
-            String::from_utf8(data.clone()).unwrap_or_default()
-        ));
+        let (actor_id,) = params;
+        log(&format!("Actor id: {}", actor_id));
 
-        let init_data: InitData = serde_json::from_slice(&data).unwrap();
-        log(&format!("Store actor id: {}", init_data.store_id));
+        let data = state.ok_or_else(|| {
+            let message =
+                "Missing init data: expected a JSON configuration with \"store_id\" or \"stores\"";
+            log(message);
+            message.to_string()
+        })?;
+        let config = Config::from_json(&data).inspect_err(|e| log(e))?;
+        log(&format!("Effective configuration: {}", config.redacted()));
 
         let initial_state = State {
-            store_id: init_data.store_id,
+            config,
+            changes: ChangeLog::default(),
+            clock: None,
         };
+        let bytes = serde_json::to_vec(&initial_state).map_err(|e| e.to_string())?;
+        Ok((Some(bytes),))
+    }
+}
+
+type Handler = fn(&RequestContext, &mut State) -> HttpResponse;
+
+fn router() -> Router<Handler> {
+    Router::<Handler>::new()
+        .get("/api/settings", settings)
+        .get("/api/stores", stores_api::list)
+        .get("/api/diff", stores_api::diff)
+        .get("/api/snapshots", snapshots::list)
+        .post("/api/snapshots", snapshots::create)
+        .get("/api/snapshots/:id", snapshots::get)
+        .post("/api/restore", restore::restore)
+        .get("/api/admin/config", admin_api::get_config)
+        .patch("/api/admin/config", admin_api::patch_config)
+        .get("/api/admin/cache", admin_api::get_cache)
+        .delete("/api/admin/cache", admin_api::clear_cache)
+        .get("/api/chain", chain::list)
+        .get("/api/chain/:hash", chain::get)
+        .get("/api/store-contents", entries_api::list)
+        .get("/api/search", entries_api::search)
+        .get("/api/query", entries_api::query)
+        .get("/api/export", export::export)
+        .post("/api/import", import::import)
+        .post("/api/entries", entries_api::create)
+        .get("/api/entries/:key", entries_api::get)
+        .put("/api/entries/:key", entries_api::update)
+        .get("/api/entries/:key/hexdump", entries_api::hexdump)
+        // Runtime content store (SPEC.md section 4.2)
+        .get("/api/content", |req, _| content_api::list_content(req))
+        .post("/api/content", |req, _| content_api::store_content(req))
+        .get("/api/content/:hash", |req, _| content_api::get_content(req))
+        .get("/api/content/:hash/exists", |req, _| {
+            content_api::content_exists(req)
+        })
+        .get("/api/stats/size", |_, _| content_api::total_size())
+        .get("/api/labels", |_, _| content_api::list_labels())
+        .get("/api/labels/:name", |req, _| content_api::get_label(req))
+        .delete("/api/labels/:name", |req, _| content_api::remove_label(req))
+        .post("/api/labels/:name/content", |req, _| {
+            content_api::put_at_label(req)
+        })
+        .put("/api/labels/:name/content", |req, _| {
+            content_api::replace_content_at_label(req)
+        })
+        .put("/api/labels/:name/content/:hash", |req, _| {
+            content_api::put_label_content(req)
+        })
+        .delete("/api/labels/:name/content/:hash", |req, _| {
+            content_api::remove_from_label(req)
+        })
+}
 
-        serde_json::to_vec(&initial_state).unwrap()
+/// The configuration the UI needs to render itself.
+fn settings(req: &RequestContext, state: &mut State) -> HttpResponse {
+    let config = &state.config;
+    response::success(json!({
+        "store_id": req.store_id,
+        "read_only": config.read_only,
+        "page_size": config.page_size,
+        "display": config.display,
+        "live": config.live,
+    }))
+}
+
+/// Whether a request writes to a store or the content store, and so is
+/// refused in read-only mode. The visualizer's own configuration, cache and
+/// snapshots stay writable, imports can still be dry runs, and restores
+/// check the `dry_run` field of their body themselves.
+fn writes_data(method: &str, path: &[String], query: &Params) -> bool {
+    if matches!(
+        method.to_ascii_uppercase().as_str(),
+        "GET" | "HEAD" | "OPTIONS"
+    ) {
+        return false;
+    }
+    match path.get(1).map(String::as_str) {
+        Some("admin" | "snapshots" | "restore") => false,
+        Some("import") => query.parse::<bool>("dry_run").ok().flatten() != Some(true),
+        _ => true,
     }
 }
 
-impl HttpGuest for Component {
-    fn handle_request(req: ServerHttpRequest, state: Json) -> (HttpResponse, Json) {
-        log(&format!("Handling HTTP request for: {}", req.uri));
-
-        match (req.method.as_str(), req.uri.as_str()) {
-            ("GET", "/") | ("GET", "/index.html") => {
-                let content = read_file("index.html").unwrap();
-                (
-                    HttpResponse {
-                        status: 200,
-                        headers: vec![("Content-Type".to_string(), "text/html".to_string())],
-                        body: Some(content),
-                    },
-                    state,
-                )
-            }
-            ("GET", "/styles.css") => {
-                let content = read_file("styles.css").unwrap();
-                (
-                    HttpResponse {
-                        status: 200,
-                        headers: vec![("Content-Type".to_string(), "text/css".to_string())],
-                        body: Some(content),
-                    },
-                    state,
-                )
-            }
-            ("GET", "/visualizer.js") => {
-                let content = read_file("visualizer.js").unwrap();
-                (
-                    HttpResponse {
-                        status: 200,
-                        headers: vec![(
-                            "Content-Type".to_string(),
-                            "application/javascript".to_string(),
-                        )],
-                        body: Some(content),
-                    },
-                    state,
-                )
-            }
-            ("GET", "/api/store-contents") => {
-                let current_state: State = serde_json::from_slice(&state).unwrap();
-                match current_state.get_all_entries() {
-                    Ok(entries) => {
-                        log(&format!("Entries: {}", entries));
-                        (
-                            HttpResponse {
-                                status: 200,
-                                headers: vec![(
-                                    "Content-Type".to_string(),
-                                    "application/json".to_string(),
-                                )],
-                                body: Some(
-                                    serde_json::to_vec(&json!({
-                                        "status": "success",
-                                        "entries": entries
-                                    }))
-                                    .unwrap(),
-                                ),
-                            },
-                            state,
-                        )
-                    }
-                    Err(_) => (
-                        HttpResponse {
-                            status: 500,
-                            headers: vec![],
-                            body: Some(b"Failed to load store contents".to_vec()),
-                        },
-                        state,
-                    ),
-                }
+fn respond(req: &ServerHttpRequest, state: &mut State) -> HttpResponse {
+    let (mut path, query) = router::split_uri(&req.uri);
+    if path.first().map(String::as_str) == Some("api") {
+        if let Err(denied) = state
+            .config
+            .auth
+            .check(router::header(&req.headers, "Authorization"))
+        {
+            return denied;
+        }
+    }
+    let store_id = match stores_api::resolve(&mut path, &state.config) {
+        Ok(store_id) => store_id,
+        Err(response) => return response,
+    };
+
+    match router().dispatch(&req.method, &path) {
+        Dispatch::Matched { .. }
+            if state.config.read_only && writes_data(&req.method, &path, &query) =>
+        {
+            response::error(403, "The visualizer is in read-only mode")
+        }
+        Dispatch::Matched { handler, params } => {
+            let ctx = RequestContext {
+                params,
+                query,
+                headers: &req.headers,
+                body: req.body.as_deref().unwrap_or_default(),
+                store_id: &store_id,
+            };
+            state.clock = time::client_time(&ctx);
+            handler(&ctx, state)
+        }
+        Dispatch::MethodNotAllowed { allow } => router::method_not_allowed(&allow),
+        Dispatch::NotFound if req.method == "GET" => {
+            let private = [state.config.snapshots.dir.as_str()];
+            match assets::serve(&path, &state.config.assets, &private) {
+                Some(asset) => caching::conditional(
+                    router::header(&req.headers, "If-None-Match"),
+                    asset,
+                    &state.config.cache.assets,
+                ),
+                None => response::not_found(),
             }
-            // Default 404 response
-            _ => (
-                HttpResponse {
-                    status: 404,
-                    headers: vec![],
-                    body: Some(b"Not Found".to_vec()),
-                },
-                state,
-            ),
         }
+        Dispatch::NotFound => response::not_found(),
     }
 }
 
-impl MessageServerClientGuest for Component {
-    fn handle_send(msg: Json, state: Json) -> Json {
-        log("Handling message server client send");
+impl HttpGuest for Component {
+    fn handle_request(
+        state: Option<Vec<u8>>,
+        params: (ServerHttpRequest,),
+    ) -> Result<(Option<Vec<u8>>, (HttpResponse,)), String> {
+        let (req,) = params;
         log(&format!(
-            "Message: {}",
-            String::from_utf8(msg).unwrap_or_default()
+            "Handling HTTP request for: {} {}",
+            req.method, req.uri
         ));
-        state
+
+        let mut current_state: State = serde_json::from_slice(state.as_deref().unwrap_or_default())
+            .map_err(|e| format!("Invalid actor state: {}", e))?;
+        let response = respond(&req, &mut current_state);
+
+        let new_state = serde_json::to_vec(&current_state).map_err(|e| e.to_string())?;
+        Ok((Some(new_state), (response,)))
+    }
+}
+
+impl MessageServerClientGuest for Component {
+    fn handle_send(state: Option<Json>, params: (Json,)) -> Result<(Option<Json>,), String> {
+        let (msg,) = params;
+        log("Handling message server client send");
+        log(&format!("Message: {}", String::from_utf8_lossy(&msg)));
+
+        let mut current_state: State = serde_json::from_slice(state.as_deref().unwrap_or_default())
+            .map_err(|e| format!("Invalid actor state: {}", e))?;
+        messages::handle_send(&mut current_state, &msg);
+
+        let new_state = serde_json::to_vec(&current_state).map_err(|e| e.to_string())?;
+        Ok((Some(new_state),))
     }
 
-    fn handle_request(msg: Json, state: Json) -> (Json, Json) {
+    fn handle_request(
+        state: Option<Json>,
+        params: (Json,),
+    ) -> Result<(Option<Json>, (Json,)), String> {
+        let (msg,) = params;
         log("Handling message server client request");
-        log(&format!(
-            "Message: {}",
-            String::from_utf8(msg).unwrap_or_default()
-        ));
-        (vec![], state)
+        log(&format!("Message: {}", String::from_utf8_lossy(&msg)));
+
+        let mut current_state: State = serde_json::from_slice(state.as_deref().unwrap_or_default())
+            .map_err(|e| format!("Invalid actor state: {}", e))?;
+        let reply = messages::handle_request(&mut current_state, &msg);
+
+        let new_state = serde_json::to_vec(&current_state).map_err(|e| e.to_string())?;
+        let reply = serde_json::to_vec(&reply).map_err(|e| e.to_string())?;
+        Ok((Some(new_state), (reply,)))
+    }
+}
+
+impl WebsocketGuest for Component {
+    fn handle_message(
+        state: Option<Vec<u8>>,
+        params: (WebsocketMessage,),
+    ) -> Result<(Option<Vec<u8>>, (WebsocketResponse,)), String> {
+        let (message,) = params;
+        log(&format!("Handling websocket message: {:?}", message.ty));
+
+        let mut current_state: State = serde_json::from_slice(state.as_deref().unwrap_or_default())
+            .map_err(|e| format!("Invalid actor state: {}", e))?;
+        let messages = websocket::handle_message(&mut current_state, message);
+
+        let new_state = serde_json::to_vec(&current_state).map_err(|e| e.to_string())?;
+        Ok((Some(new_state), (WebsocketResponse { messages },)))
     }
 }
 
 bindings::export!(Component with_types_in bindings);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::path;
+
+    fn writes(method: &str, uri: &str) -> bool {
+        let (path, query) = router::split_uri(uri);
+        assert!(
+            matches!(router().dispatch(method, &path), Dispatch::Matched { .. }),
+            "{} {} is not a route",
+            method,
+            uri
+        );
+        writes_data(method, &path, &query)
+    }
+
+    #[test]
+    fn read_only_covers_every_data_write() {
+        for (method, uri) in [
+            ("POST", "/api/entries"),
+            ("PUT", "/api/entries/k"),
+            ("POST", "/api/import"),
+            ("POST", "/api/import?dry_run=false"),
+            ("POST", "/api/content"),
+            ("DELETE", "/api/labels/l"),
+            ("POST", "/api/labels/l/content"),
+            ("PUT", "/api/labels/l/content"),
+            ("PUT", "/api/labels/l/content/h"),
+            ("DELETE", "/api/labels/l/content/h"),
+        ] {
+            assert!(writes(method, uri), "{} {}", method, uri);
+        }
+    }
+
+    #[test]
+    fn read_only_leaves_reads_and_own_settings_alone() {
+        for (method, uri) in [
+            ("GET", "/api/entries/k"),
+            ("GET", "/api/content"),
+            ("POST", "/api/import?dry_run=true"),
+            ("POST", "/api/restore"),
+            ("POST", "/api/snapshots"),
+            ("PATCH", "/api/admin/config"),
+            ("DELETE", "/api/admin/cache"),
+        ] {
+            assert!(!writes(method, uri), "{} {}", method, uri);
+        }
+        assert!(!writes_data(
+            "get",
+            &path(&["api", "labels", "l"]),
+            &Params::default()
+        ));
+    }
+}
@@ -0,0 +1,297 @@
+//! Sorting and cursor pagination over key-value store entries.
+
+use crate::content_type;
+use crate::router::{ParamError, Params};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::cmp::Ordering;
+
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+pub const MAX_PAGE_SIZE: usize = 1000;
+
+/// A single key/value pair as returned by the store actor's `All` action.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Entry {
+    pub key: String,
+    pub value: Vec<u8>,
+}
+
+impl Entry {
+    /// The entry as listed: its size and content type and, for JSON and text,
+    /// the first `preview_length` characters (JSON compacted). Values
+    /// themselves are fetched one at a time through `/api/entries/:key`.
+    pub fn to_json(&self, preview_length: usize) -> Value {
+        let content_type = content_type::detect(&self.value);
+        let text = match content_type.category() {
+            "json" => serde_json::from_slice::<Value>(&self.value)
+                .ok()
+                .map(|value| value.to_string()),
+            "text" => std::str::from_utf8(&self.value).ok().map(str::to_string),
+            _ => None,
+        };
+        let truncated = text
+            .as_ref()
+            .is_some_and(|text| text.chars().count() > preview_length);
+        let preview = text.map(|text| text.chars().take(preview_length).collect::<String>());
+        json!({
+            "key": self.key,
+            "size": self.value.len(),
+            "content_type": content_type.as_str(),
+            "category": content_type.category(),
+            "preview": preview,
+            "preview_truncated": truncated,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortKey {
+    Key,
+    Size,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Order {
+    Asc,
+    Desc,
+}
+
+fn invalid(name: &str, message: &str) -> ParamError {
+    ParamError {
+        name: name.to_string(),
+        message: message.to_string(),
+    }
+}
+
+/// Position of an entry in the sort order. Serialized as `<size>:<key>`.
+#[derive(Debug, Clone, PartialEq)]
+struct Cursor {
+    size: usize,
+    key: String,
+}
+
+impl Cursor {
+    fn of(entry: &Entry) -> Self {
+        Cursor {
+            size: entry.value.len(),
+            key: entry.key.clone(),
+        }
+    }
+
+    fn parse(value: &str) -> Result<Self, ParamError> {
+        let (size, key) = value
+            .split_once(':')
+            .ok_or_else(|| invalid("cursor", "expected <size>:<key>"))?;
+        let size = size
+            .parse()
+            .map_err(|_| invalid("cursor", "expected <size>:<key>"))?;
+        Ok(Cursor {
+            size,
+            key: key.to_string(),
+        })
+    }
+
+    fn encode(&self) -> String {
+        format!("{}:{}", self.size, self.key)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ListOptions {
+    pub limit: usize,
+    pub offset: usize,
+    cursor: Option<Cursor>,
+    pub sort: SortKey,
+    pub order: Order,
+}
+
+impl ListOptions {
+    /// Reads `limit`, `offset`, `cursor`, `sort` and `order` from a query string.
+    pub fn from_query(query: &Params, default_limit: usize) -> Result<Self, ParamError> {
+        let limit = query
+            .parse::<usize>("limit")?
+            .unwrap_or(default_limit)
+            .clamp(1, MAX_PAGE_SIZE);
+        let offset = query.parse::<usize>("offset")?.unwrap_or(0);
+        let cursor = query.get("cursor").map(Cursor::parse).transpose()?;
+        let sort = match query.get("sort").unwrap_or("key") {
+            "key" => SortKey::Key,
+            "size" => SortKey::Size,
+            _ => return Err(invalid("sort", "expected key or size")),
+        };
+        let order = match query.get("order").unwrap_or("asc") {
+            "asc" => Order::Asc,
+            "desc" => Order::Desc,
+            _ => return Err(invalid("order", "expected asc or desc")),
+        };
+        Ok(ListOptions {
+            limit,
+            offset,
+            cursor,
+            sort,
+            order,
+        })
+    }
+
+    fn compare(&self, a: &Cursor, b: &Cursor) -> Ordering {
+        let ordering = match self.sort {
+            SortKey::Key => a.key.cmp(&b.key),
+            SortKey::Size => a.size.cmp(&b.size).then_with(|| a.key.cmp(&b.key)),
+        };
+        match self.order {
+            Order::Asc => ordering,
+            Order::Desc => ordering.reverse(),
+        }
+    }
+}
+
+pub struct Page {
+    pub entries: Vec<Entry>,
+    pub total: usize,
+    pub next_cursor: Option<String>,
+}
+
+impl Page {
+    pub fn to_json(&self, preview_length: usize) -> Value {
+        json!({
+            "entries": self
+                .entries
+                .iter()
+                .map(|entry| entry.to_json(preview_length))
+                .collect::<Vec<_>>(),
+            "total": self.total,
+            "next_cursor": self.next_cursor,
+        })
+    }
+}
+
+/// Sorts `entries` and cuts out the page described by `options`. A cursor
+/// takes precedence over `offset`; the offset is then applied after it.
+pub fn paginate(mut entries: Vec<Entry>, options: &ListOptions) -> Page {
+    let total = entries.len();
+    entries.sort_by(|a, b| options.compare(&Cursor::of(a), &Cursor::of(b)));
+
+    let start = match &options.cursor {
+        Some(cursor) => entries
+            .iter()
+            .position(|e| options.compare(&Cursor::of(e), cursor) == Ordering::Greater)
+            .unwrap_or(total),
+        None => 0,
+    };
+    let start = start.saturating_add(options.offset).min(total);
+    let end = start.saturating_add(options.limit).min(total);
+
+    let next_cursor = if end < total && end > start {
+        Some(Cursor::of(&entries[end - 1]).encode())
+    } else {
+        None
+    };
+    entries.truncate(end);
+    let entries = entries.split_off(start);
+
+    Page {
+        entries,
+        total,
+        next_cursor,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries() -> Vec<Entry> {
+        [("b", 3), ("a:1", 1), ("d", 3), ("c", 2), ("e", 0)]
+            .iter()
+            .map(|(key, size)| Entry {
+                key: key.to_string(),
+                value: vec![0; *size],
+            })
+            .collect()
+    }
+
+    fn options(query: &str) -> ListOptions {
+        ListOptions::from_query(&Params::from_query(query), DEFAULT_PAGE_SIZE).unwrap()
+    }
+
+    fn keys(page: &Page) -> Vec<&str> {
+        page.entries.iter().map(|e| e.key.as_str()).collect()
+    }
+
+    /// Follows `next_cursor` from the first page to the last.
+    fn walk(query: &str) -> Vec<String> {
+        let mut seen = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let query = match &cursor {
+                Some(c) => format!("{}&cursor={}", query, c),
+                None => query.to_string(),
+            };
+            let page = paginate(entries(), &options(&query));
+            assert_eq!(page.total, 5);
+            seen.extend(page.entries.iter().map(|e| e.key.clone()));
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => return seen,
+            }
+        }
+    }
+
+    #[test]
+    fn cursor_paging_visits_every_entry_once() {
+        assert_eq!(walk("limit=2"), ["a:1", "b", "c", "d", "e"]);
+        assert_eq!(
+            walk("limit=2&sort=size&order=desc"),
+            ["d", "b", "c", "a:1", "e"]
+        );
+        assert_eq!(walk("limit=1&sort=size"), ["e", "a:1", "c", "b", "d"]);
+    }
+
+    #[test]
+    fn offset_applies_after_the_cursor() {
+        let page = paginate(entries(), &options("limit=2&offset=1"));
+        assert_eq!(keys(&page), ["b", "c"]);
+        assert_eq!(page.next_cursor.as_deref(), Some("2:c"));
+
+        let page = paginate(entries(), &options("cursor=3:b&offset=1"));
+        assert_eq!(keys(&page), ["d", "e"]);
+        assert_eq!(page.next_cursor, None);
+
+        let page = paginate(entries(), &options("offset=10"));
+        assert!(page.entries.is_empty());
+        assert_eq!(page.next_cursor, None);
+    }
+
+    #[test]
+    fn options_are_validated_and_clamped() {
+        assert_eq!(options("limit=0").limit, 1);
+        assert_eq!(options("limit=5000").limit, MAX_PAGE_SIZE);
+        let parse = |q: &str| ListOptions::from_query(&Params::from_query(q), 10);
+        assert_eq!(parse("").unwrap().limit, 10);
+        assert_eq!(parse("sort=name").unwrap_err().name, "sort");
+        assert_eq!(parse("order=up").unwrap_err().name, "order");
+        assert_eq!(parse("cursor=nokey").unwrap_err().name, "cursor");
+        assert_eq!(parse("cursor=x:a").unwrap_err().name, "cursor");
+    }
+
+    #[test]
+    fn listed_entries_carry_a_preview_not_the_value() {
+        let json = crate::test_support::entry("j", r#"{ "name": "Ada",  "id": 7 }"#).to_json(10);
+        assert_eq!(json["preview"], r#"{"id":7,"n"#);
+        assert_eq!(json["preview_truncated"], true);
+        assert!(json.get("value").is_none());
+
+        let text = crate::test_support::entry("t", "héllo").to_json(10);
+        assert_eq!(text["preview"], "héllo");
+        assert_eq!(text["preview_truncated"], false);
+
+        let binary = Entry {
+            key: "b".to_string(),
+            value: vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'],
+        }
+        .to_json(10);
+        assert_eq!(binary["category"], "image");
+        assert!(binary["preview"].is_null());
+        assert_eq!(binary["size"], 8);
+    }
+}
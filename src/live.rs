@@ -0,0 +1,328 @@
+//! Change tracking behind the live updates.
+//!
+//! The change log records every added, changed or removed key the visualizer
+//! learns about, each under a sequence number. Clients remember the last
+//! number they have seen and ask for everything after it, which works without
+//! per-connection server state.
+//!
+//! Changes are found either by comparing full listings (`observe`) or from
+//! notifications sent by the store actor (`apply`). Both keep a fingerprint
+//! of every key, the visualizer's view of each store. The views can be large,
+//! so like the entry cache they live in the instance's memory; only the
+//! sequence number and the bounded change log are part of the actor state.
+//! An instance that starts without a view takes the next listing as its
+//! baseline.
+
+use crate::content_type;
+use crate::listing::Entry;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha1::{Digest, Sha1};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
+
+/// Changes kept for clients that fall behind; older ones force a resync.
+const MAX_CHANGES: usize = 1000;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct LiveConfig {
+    /// Websocket URL for the UI. Defaults to port 8091 on the page's host.
+    pub websocket_url: Option<String>,
+    /// How often the UI asks for new changes.
+    pub poll_interval_ms: u64,
+}
+
+impl Default for LiveConfig {
+    fn default() -> Self {
+        LiveConfig {
+            websocket_url: None,
+            poll_interval_ms: 2000,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    #[serde(rename = "entry_added")]
+    Added,
+    #[serde(rename = "entry_changed")]
+    Changed,
+    #[serde(rename = "entry_removed")]
+    Removed,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Change {
+    pub seq: u64,
+    pub store_id: String,
+    pub key: String,
+    #[serde(rename = "type")]
+    pub kind: ChangeKind,
+    pub size: Option<usize>,
+    pub content_type: Option<String>,
+    /// What revealed the change: `poll` or `notification`.
+    pub source: String,
+}
+
+impl Change {
+    pub fn to_json(&self, store: &str) -> Value {
+        json!({
+            "type": self.kind,
+            "seq": self.seq,
+            "store": store,
+            "key": self.key,
+            "size": self.size,
+            "content_type": self.content_type,
+            "source": self.source,
+        })
+    }
+}
+
+/// A short hash of a value, enough to notice that it changed.
+fn fingerprint(value: &[u8]) -> String {
+    format!("{:x}", Sha1::digest(value))[..16].to_string()
+}
+
+#[derive(Debug, Default)]
+struct Views {
+    /// Last known fingerprint of every key, per store actor. Keys announced
+    /// without a value have an empty fingerprint.
+    fingerprints: BTreeMap<String, BTreeMap<String, String>>,
+    /// Store actors that have sent change notifications.
+    notifying: BTreeSet<String>,
+}
+
+thread_local! {
+    static VIEWS: RefCell<Views> = RefCell::new(Views::default());
+}
+
+fn with_views<R>(f: impl FnOnce(&mut Views) -> R) -> R {
+    VIEWS.with(|views| f(&mut views.borrow_mut()))
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct ChangeLog {
+    last_seq: u64,
+    changes: VecDeque<Change>,
+}
+
+impl ChangeLog {
+    /// Sequence number of the newest change.
+    pub fn version(&self) -> u64 {
+        self.last_seq
+    }
+
+    pub fn record(
+        &mut self,
+        store_id: &str,
+        key: &str,
+        kind: ChangeKind,
+        value: Option<&[u8]>,
+        source: &str,
+    ) -> u64 {
+        self.last_seq += 1;
+        self.changes.push_back(Change {
+            seq: self.last_seq,
+            store_id: store_id.to_string(),
+            key: key.to_string(),
+            kind,
+            size: value.map(<[u8]>::len),
+            content_type: value.map(|v| content_type::detect(v).as_str().to_string()),
+            source: source.to_string(),
+        });
+        while self.changes.len() > MAX_CHANGES {
+            self.changes.pop_front();
+        }
+        self.last_seq
+    }
+
+    /// Whether `store_id` has a baseline to compare new listings against.
+    pub fn is_tracked(&self, store_id: &str) -> bool {
+        with_views(|views| views.fingerprints.contains_key(store_id))
+    }
+
+    /// Whether a poll has to list `store_id` to find changes. Stores that
+    /// send notifications only need a listing for the baseline.
+    pub fn needs_rescan(&self, store_id: &str) -> bool {
+        with_views(|views| {
+            !views.fingerprints.contains_key(store_id) || !views.notifying.contains(store_id)
+        })
+    }
+
+    /// Records a change announced by a notification and updates the view.
+    /// `kind` is corrected against the view (a change to an unknown key is an
+    /// addition), and announcements the view already reflects are dropped.
+    /// Returns the change's sequence number, if one was recorded.
+    pub fn apply(
+        &mut self,
+        store_id: &str,
+        key: &str,
+        kind: ChangeKind,
+        value: Option<&[u8]>,
+        source: &str,
+    ) -> Option<u64> {
+        let kind = with_views(|views| {
+            views.notifying.insert(store_id.to_string());
+            // Without a baseline, a partial view would make the first full
+            // listing report every other key as added.
+            let Some(view) = views.fingerprints.get_mut(store_id) else {
+                return Some(kind);
+            };
+            match kind {
+                ChangeKind::Removed => view.remove(key).map(|_| ChangeKind::Removed),
+                ChangeKind::Added | ChangeKind::Changed => {
+                    let new = value.map(fingerprint).unwrap_or_default();
+                    match view.insert(key.to_string(), new.clone()) {
+                        Some(old) if !new.is_empty() && old == new => None,
+                        Some(_) => Some(ChangeKind::Changed),
+                        None => Some(ChangeKind::Added),
+                    }
+                }
+            }
+        })?;
+        Some(self.record(store_id, key, kind, value, source))
+    }
+
+    /// Compares a full listing with the last one seen and records the
+    /// differences. The first listing of a store only sets the baseline.
+    pub fn observe(&mut self, store_id: &str, entries: &[Entry], source: &str) {
+        let current: BTreeMap<String, String> = entries
+            .iter()
+            .map(|e| (e.key.clone(), fingerprint(&e.value)))
+            .collect();
+        let previous = with_views(|views| views.fingerprints.insert(store_id.to_string(), current));
+        let Some(previous) = previous else {
+            return;
+        };
+        for entry in entries {
+            let kind = match previous.get(&entry.key) {
+                None => ChangeKind::Added,
+                Some(old) if *old != fingerprint(&entry.value) => ChangeKind::Changed,
+                Some(_) => continue,
+            };
+            self.record(store_id, &entry.key, kind, Some(&entry.value), source);
+        }
+        let present: HashSet<&str> = entries.iter().map(|e| e.key.as_str()).collect();
+        let removed: Vec<String> = previous
+            .into_keys()
+            .filter(|key| !present.contains(key.as_str()))
+            .collect();
+        for key in removed {
+            self.record(store_id, &key, ChangeKind::Removed, None, source);
+        }
+    }
+
+    /// Changes to `store_id` after `since`, or `None` if some of them have
+    /// already been dropped from the log (or `since` is from another run).
+    pub fn since(&self, store_id: &str, since: u64) -> Option<Vec<&Change>> {
+        let oldest = self.changes.front().map_or(self.last_seq + 1, |c| c.seq);
+        if since + 1 < oldest || since > self.last_seq {
+            return None;
+        }
+        Some(
+            self.changes
+                .iter()
+                .filter(|c| c.seq > since && c.store_id == store_id)
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::entries;
+
+    fn kinds(changes: &[&Change]) -> Vec<(u64, String, ChangeKind)> {
+        changes
+            .iter()
+            .map(|c| (c.seq, c.key.clone(), c.kind))
+            .collect()
+    }
+
+    #[test]
+    fn observe_sets_baseline_then_records_differences() {
+        let mut log = ChangeLog::default();
+        log.observe("s", &entries(&[("a", "1"), ("b", "2")]), "poll");
+        assert_eq!(log.version(), 0);
+        assert!(log.is_tracked("s"));
+
+        log.observe("s", &entries(&[("a", "1"), ("b", "3"), ("c", "4")]), "poll");
+        log.observe("s", &entries(&[("b", "3"), ("c", "4")]), "poll");
+        assert_eq!(
+            kinds(&log.since("s", 0).unwrap()),
+            [
+                (1, "b".to_string(), ChangeKind::Changed),
+                (2, "c".to_string(), ChangeKind::Added),
+                (3, "a".to_string(), ChangeKind::Removed),
+            ]
+        );
+        assert_eq!(log.since("s", 2).unwrap().len(), 1);
+        assert!(log.since("other", 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn apply_corrects_kinds_and_drops_repeats() {
+        let mut log = ChangeLog::default();
+        log.observe("s", &entries(&[("a", "1")]), "poll");
+        assert!(log.needs_rescan("s"));
+
+        assert_eq!(
+            log.apply("s", "a", ChangeKind::Added, Some(b"2"), "notification"),
+            Some(1)
+        );
+        assert_eq!(
+            log.apply("s", "a", ChangeKind::Changed, Some(b"2"), "notification"),
+            None
+        );
+        assert_eq!(
+            log.apply("s", "b", ChangeKind::Changed, None, "notification"),
+            Some(2)
+        );
+        assert_eq!(
+            log.apply("s", "zz", ChangeKind::Removed, None, "notification"),
+            None
+        );
+        assert!(!log.needs_rescan("s"));
+        assert_eq!(
+            kinds(&log.since("s", 0).unwrap()),
+            [
+                (1, "a".to_string(), ChangeKind::Changed),
+                (2, "b".to_string(), ChangeKind::Added),
+            ]
+        );
+    }
+
+    #[test]
+    fn views_are_not_serialized() {
+        let mut log = ChangeLog::default();
+        log.observe("s", &entries(&[("a", "1")]), "poll");
+        log.observe("s", &entries(&[("a", "2")]), "poll");
+        let state = serde_json::to_value(&log).unwrap();
+        assert_eq!(state["last_seq"], 1);
+        assert!(state.get("fingerprints").is_none());
+
+        // A restored log keeps the instance's views.
+        let restored: ChangeLog = serde_json::from_value(state).unwrap();
+        assert!(restored.is_tracked("s"));
+    }
+
+    #[test]
+    fn since_asks_for_resync_when_changes_were_dropped() {
+        let mut log = ChangeLog::default();
+        assert!(log.since("s", 0).unwrap().is_empty());
+        assert!(log.since("s", 1).is_none());
+
+        for i in 0..MAX_CHANGES + 5 {
+            log.record("s", &i.to_string(), ChangeKind::Added, None, "poll");
+        }
+        let last = log.version();
+        assert!(log.since("s", 0).is_none());
+        assert!(log.since("s", 4).is_none());
+        assert_eq!(log.since("s", 5).unwrap().len(), MAX_CHANGES);
+        assert!(log.since("s", last).unwrap().is_empty());
+        assert!(log.since("s", last + 1).is_none());
+    }
+}
@@ -0,0 +1,379 @@
+//! JSON protocols spoken over `message-server-client`.
+//!
+//! Requests (`handle_request`) are objects tagged by `type`; replies mirror
+//! the HTTP API's `{"status": "success", ...}` /
+//! `{"status": "error", "code", "message"}`. Besides the configuration they
+//! give other actors the listing, search and snapshot features without going
+//! through HTTP. Listing and search take the same options as the HTTP query
+//! string (`limit`, `cursor`, `sort`, `prefix`, `glob`, ...) as fields of
+//! the request, with `content_type` standing in for `type`. `store` names a
+//! registered store.
+//!
+//! Notifications (`handle_send`) tell the visualizer that a key changed, so
+//! connected browsers hear about it without the store being listed again.
+//! They are also tagged by `type` and can be sent one at a time or as an
+//! array.
+
+use crate::bindings::ntwk::theater::runtime::log;
+use crate::encoding::{self, ValueEncoding};
+use crate::listing::{self, Entry, ListOptions};
+use crate::live::ChangeKind;
+use crate::router::{ParamError, Params};
+use crate::search::SearchFilter;
+use crate::{admin_api, cache, content_type, response, snapshots, State};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientRequest {
+    GetConfig,
+    /// `patch` is a JSON merge patch, as for `PATCH /api/admin/config`.
+    /// `token` must match `auth.token`.
+    UpdateConfig {
+        patch: Value,
+        token: Option<String>,
+    },
+    /// One page of keys, with the search filters applied.
+    ListKeys {
+        store: Option<String>,
+        #[serde(flatten)]
+        options: Map<String, Value>,
+    },
+    GetEntry {
+        store: Option<String>,
+        key: String,
+    },
+    /// One page of entries with their values, with the search filters applied.
+    Search {
+        store: Option<String>,
+        #[serde(flatten)]
+        options: Map<String, Value>,
+    },
+    /// Entry counts and sizes, overall and per content type.
+    Stats {
+        store: Option<String>,
+        #[serde(flatten)]
+        options: Map<String, Value>,
+    },
+    /// Writes a snapshot. `time` (ms since the epoch) names it by date.
+    Snapshot {
+        store: Option<String>,
+        time: Option<u64>,
+    },
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type")]
+pub enum Notification {
+    #[serde(rename = "entry_added")]
+    Added(KeyNotification),
+    #[serde(rename = "entry_changed")]
+    Changed(KeyNotification),
+    #[serde(rename = "entry_removed")]
+    Removed(KeyNotification),
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct KeyNotification {
+    /// Registered store name. Defaults to the default store.
+    store: Option<String>,
+    /// Store actor id, as an alternative to `store`.
+    store_id: Option<String>,
+    key: String,
+    /// The new value, if the sender includes it.
+    value: Option<Value>,
+    #[serde(default = "default_encoding")]
+    encoding: ValueEncoding,
+}
+
+fn default_encoding() -> ValueEncoding {
+    ValueEncoding::Json
+}
+
+impl Notification {
+    fn parts(self) -> (ChangeKind, KeyNotification) {
+        match self {
+            Notification::Added(n) => (ChangeKind::Added, n),
+            Notification::Changed(n) => (ChangeKind::Changed, n),
+            Notification::Removed(n) => (ChangeKind::Removed, n),
+        }
+    }
+}
+
+/// The actor id of the registered store a notification is about.
+fn notified_store(state: &State, notification: &KeyNotification) -> Result<String, String> {
+    match (&notification.store, &notification.store_id) {
+        (Some(_), Some(_)) => Err("give either store or store_id, not both".to_string()),
+        (None, Some(store_id)) => {
+            if state.config.registry().values().any(|id| id == store_id) {
+                Ok(store_id.clone())
+            } else {
+                Err(format!("store actor {} is not registered", store_id))
+            }
+        }
+        (store, None) => {
+            let name = store
+                .clone()
+                .unwrap_or_else(|| state.config.default_store_name());
+            state
+                .config
+                .store(&name)
+                .ok_or_else(|| format!("no store named '{}'", name))
+        }
+    }
+}
+
+fn apply_notification(state: &mut State, notification: Notification) -> Result<(), String> {
+    let (kind, notification) = notification.parts();
+    let store_id = notified_store(state, &notification)?;
+    let value = match (&notification.value, kind) {
+        (Some(_), ChangeKind::Removed) => {
+            return Err("entry_removed cannot carry a value".to_string());
+        }
+        (Some(value), _) => Some(
+            encoding::decode_value(notification.encoding, value)
+                .map_err(|e| format!("value for {}: {}", notification.key, e))?,
+        ),
+        (None, _) => None,
+    };
+    cache::with(|c| match (kind, &value) {
+        (ChangeKind::Removed, _) => c.update(&store_id, &notification.key, None),
+        (_, Some(value)) => c.update(&store_id, &notification.key, Some(value)),
+        (_, None) => c.invalidate(&store_id),
+    });
+    match state.changes.apply(
+        &store_id,
+        &notification.key,
+        kind,
+        value.as_deref(),
+        "notification",
+    ) {
+        Some(seq) => log(&format!(
+            "Recorded change {} to {} in {}",
+            seq, notification.key, store_id
+        )),
+        None => log(&format!(
+            "Change to {} in {} was already known",
+            notification.key, store_id
+        )),
+    }
+    Ok(())
+}
+
+/// Applies one notification or an array of them. There is no reply to send,
+/// so problems are logged and the rest of a batch still applies.
+pub fn handle_send(state: &mut State, msg: &[u8]) {
+    let notifications = match serde_json::from_slice::<Value>(msg) {
+        Ok(Value::Array(items)) => items,
+        Ok(item) => vec![item],
+        Err(e) => {
+            log(&format!("Ignoring notification: {}", e));
+            return;
+        }
+    };
+    for item in notifications {
+        let result = serde_json::from_value::<Notification>(item)
+            .map_err(|e| e.to_string())
+            .and_then(|notification| apply_notification(state, notification));
+        if let Err(e) = result {
+            log(&format!("Ignoring notification: {}", e));
+        }
+    }
+}
+
+fn invalid_parameter(e: ParamError) -> Value {
+    response::error_body(
+        "invalid_parameter",
+        &format!("Invalid parameter '{}': {}", e.name, e.message),
+    )
+}
+
+/// Turns request fields into query parameters so that they are read exactly
+/// like the HTTP API's query string.
+fn to_params(options: Map<String, Value>) -> Result<Params, Value> {
+    let mut pairs = Vec::new();
+    for (name, value) in options {
+        let value = match value {
+            Value::Null => continue,
+            Value::String(s) => s,
+            Value::Number(n) => n.to_string(),
+            Value::Bool(b) => b.to_string(),
+            _ => {
+                return Err(invalid_parameter(ParamError {
+                    name,
+                    message: "expected a string, number or boolean".to_string(),
+                }))
+            }
+        };
+        // `type` is taken by the message tag.
+        let name = match name.as_str() {
+            "content_type" => "type".to_string(),
+            _ => name,
+        };
+        pairs.push((name, value));
+    }
+    Ok(Params::from_pairs(pairs))
+}
+
+/// Resolves `store` (the default store if absent) to its name and actor id.
+fn resolve_store(state: &State, store: Option<String>) -> Result<(String, String), Value> {
+    let name = store.unwrap_or_else(|| state.config.default_store_name());
+    match state.config.store(&name) {
+        Some(store_id) => Ok((name, store_id)),
+        None => Err(response::error_body(
+            "unknown_store",
+            &format!("No store named '{}'", name),
+        )),
+    }
+}
+
+/// The entries of `store_id` that pass the filters in `params`.
+fn filtered_entries(
+    state: &mut State,
+    store_id: &str,
+    params: &Params,
+) -> Result<Vec<Entry>, Value> {
+    let filter = SearchFilter::from_query(params).map_err(invalid_parameter)?;
+    let entries = state
+        .get_all_entries(store_id)
+        .map_err(|e| response::error_body(e.code(), &e.to_string()))?;
+    Ok(filter.apply(entries))
+}
+
+fn encoded_entry(entry: &Entry) -> Value {
+    let (encoding, value) = encoding::encode_value(&entry.value);
+    json!({
+        "key": entry.key,
+        "size": entry.value.len(),
+        "content_type": content_type::detect(&entry.value).as_str(),
+        "encoding": encoding,
+        "value": value,
+    })
+}
+
+fn stats(entries: &[Entry]) -> Value {
+    let mut by_type: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+    let mut by_category: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+    for entry in entries {
+        let detected = content_type::detect(&entry.value);
+        for (totals, name) in [
+            (&mut by_type, detected.as_str()),
+            (&mut by_category, detected.category()),
+        ] {
+            let (count, size) = totals.entry(name).or_default();
+            *count += 1;
+            *size += entry.value.len();
+        }
+    }
+    let breakdown = |totals: BTreeMap<&str, (usize, usize)>| -> Map<String, Value> {
+        totals
+            .into_iter()
+            .map(|(name, (count, size))| {
+                (
+                    name.to_string(),
+                    json!({ "count": count, "total_size": size }),
+                )
+            })
+            .collect()
+    };
+
+    let total_size: usize = entries.iter().map(|e| e.value.len()).sum();
+    let largest = entries.iter().max_by_key(|e| e.value.len());
+    json!({
+        "count": entries.len(),
+        "total_size": total_size,
+        "min_size": entries.iter().map(|e| e.value.len()).min(),
+        "max_size": largest.map(|e| e.value.len()),
+        "mean_size": (!entries.is_empty()).then(|| total_size as f64 / entries.len() as f64),
+        "largest_key": largest.map(|e| &e.key),
+        "by_type": breakdown(by_type),
+        "by_category": breakdown(by_category),
+    })
+}
+
+fn answer(state: &mut State, request: ClientRequest) -> Result<Value, Value> {
+    match request {
+        ClientRequest::GetConfig => Ok(json!({ "config": state.config.redacted() })),
+        ClientRequest::UpdateConfig { patch, token } => {
+            if !state.config.auth.accepts(token.as_deref()) {
+                return Err(response::error_body(
+                    "unauthorized",
+                    "Missing or invalid token",
+                ));
+            }
+            admin_api::update_config(state, &patch)
+                .map(|config| json!({ "config": config }))
+                .map_err(|e| response::error_body(e.code(), &e.to_string()))
+        }
+        ClientRequest::ListKeys { store, options } => {
+            let (name, store_id) = resolve_store(state, store)?;
+            let params = to_params(options)?;
+            let list_options = ListOptions::from_query(&params, state.config.page_size)
+                .map_err(invalid_parameter)?;
+            let page =
+                listing::paginate(filtered_entries(state, &store_id, &params)?, &list_options);
+            Ok(json!({
+                "store": name,
+                "keys": page.entries.iter().map(|e| &e.key).collect::<Vec<_>>(),
+                "total": page.total,
+                "next_cursor": page.next_cursor,
+            }))
+        }
+        ClientRequest::GetEntry { store, key } => {
+            let (name, store_id) = resolve_store(state, store)?;
+            match state.get_entry(&store_id, &key) {
+                Ok(Some(value)) => Ok(json!({
+                    "store": name,
+                    "entry": encoded_entry(&Entry { key, value }),
+                })),
+                Ok(None) => Err(response::error_body(
+                    "not_found",
+                    &format!("No entry with key {}", key),
+                )),
+                Err(e) => Err(response::error_body(e.code(), &e.to_string())),
+            }
+        }
+        ClientRequest::Search { store, options } => {
+            let (name, store_id) = resolve_store(state, store)?;
+            let params = to_params(options)?;
+            let list_options = ListOptions::from_query(&params, state.config.page_size)
+                .map_err(invalid_parameter)?;
+            let page =
+                listing::paginate(filtered_entries(state, &store_id, &params)?, &list_options);
+            Ok(json!({
+                "store": name,
+                "entries": page.entries.iter().map(encoded_entry).collect::<Vec<_>>(),
+                "total": page.total,
+                "next_cursor": page.next_cursor,
+            }))
+        }
+        ClientRequest::Stats { store, options } => {
+            let (name, store_id) = resolve_store(state, store)?;
+            let params = to_params(options)?;
+            let entries = filtered_entries(state, &store_id, &params)?;
+            Ok(json!({ "store": name, "stats": stats(&entries) }))
+        }
+        ClientRequest::Snapshot { store, time } => {
+            let (_, store_id) = resolve_store(state, store)?;
+            snapshots::take(state, &store_id, time)
+                .map(|manifest| json!({ "snapshot": manifest }))
+                .map_err(|e| response::error_body(e.code(), &e.to_string()))
+        }
+    }
+}
+
+/// Answers one request. Malformed requests get an error reply rather than
+/// failing the call, so the sender always learns what went wrong.
+pub fn handle_request(state: &mut State, msg: &[u8]) -> Value {
+    let request: ClientRequest = match serde_json::from_slice(msg) {
+        Ok(request) => request,
+        Err(e) => return response::error_body("bad_request", &format!("Invalid request: {}", e)),
+    };
+    match answer(state, request) {
+        Ok(body) => response::success_body(body),
+        Err(reply) => reply,
+    }
+}
@@ -0,0 +1,155 @@
+//! Typed request/response protocol of the key-value store actor.
+
+use crate::bindings::exports::ntwk::theater::http_server::HttpResponse;
+use crate::bindings::ntwk::theater::message_server_host::request;
+use crate::listing::Entry;
+use crate::response;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+
+// Reuse the Request/Action types from key-value actor
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Request {
+    pub _type: String,
+    pub data: Action,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Action {
+    Get(String),
+    Put(Vec<u8>),
+    All(()),
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum Response {
+    Ok {
+        data: ResponseData,
+    },
+    Error {
+        #[serde(default)]
+        message: Option<String>,
+        #[serde(default)]
+        data: Value,
+    },
+}
+
+#[derive(Deserialize, Debug)]
+pub enum ResponseData {
+    Get { data: Option<Vec<u8>> },
+    Put { data: Option<String> },
+    All { data: Vec<Entry> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StoreError {
+    /// The message-server request itself failed (actor missing, crashed, ...).
+    Transport(String),
+    /// The store actor answered with a non-`ok` status.
+    Rejected(String),
+    /// The answer could not be decoded, or did not match the action sent.
+    Decode(String),
+}
+
+impl StoreError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            StoreError::Transport(_) => "store_unreachable",
+            StoreError::Rejected(_) => "store_rejected",
+            StoreError::Decode(_) => "store_bad_response",
+        }
+    }
+
+    pub fn http_status(&self) -> u16 {
+        match self {
+            StoreError::Transport(_) => 503,
+            StoreError::Rejected(_) | StoreError::Decode(_) => 502,
+        }
+    }
+
+    pub fn into_response(self) -> HttpResponse {
+        response::error_with_code(self.http_status(), self.code(), &self.to_string())
+    }
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreError::Transport(e) => write!(f, "Could not reach the store actor: {}", e),
+            StoreError::Rejected(e) => write!(f, "The store actor rejected the request: {}", e),
+            StoreError::Decode(e) => write!(f, "Unexpected response from the store actor: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+/// Sends `action` to the store actor `store_id` and decodes the reply.
+pub fn send(store_id: &str, action: Action) -> Result<ResponseData, StoreError> {
+    let req = Request {
+        _type: "request".to_string(),
+        data: action,
+    };
+    let request_bytes = serde_json::to_vec(&req).map_err(|e| StoreError::Decode(e.to_string()))?;
+    let response_bytes = request(store_id, &request_bytes).map_err(StoreError::Transport)?;
+
+    match serde_json::from_slice(&response_bytes) {
+        Ok(Response::Ok { data }) => Ok(data),
+        Ok(Response::Error { message, data }) => Err(StoreError::Rejected(
+            message.unwrap_or_else(|| data.to_string()),
+        )),
+        Err(e) => Err(StoreError::Decode(e.to_string())),
+    }
+}
+
+impl ResponseData {
+    fn kind(&self) -> &'static str {
+        match self {
+            ResponseData::Get { .. } => "Get",
+            ResponseData::Put { .. } => "Put",
+            ResponseData::All { .. } => "All",
+        }
+    }
+}
+
+fn unexpected(expected: &str, got: &ResponseData) -> StoreError {
+    StoreError::Decode(format!(
+        "expected a {} response, got {}",
+        expected,
+        got.kind()
+    ))
+}
+
+pub fn get_all(store_id: &str) -> Result<Vec<Entry>, StoreError> {
+    match send(store_id, Action::All(()))? {
+        ResponseData::All { data } => Ok(data),
+        other => Err(unexpected("All", &other)),
+    }
+}
+
+/// A single value; `None` if the store has no such key.
+pub fn get(store_id: &str, key: &str) -> Result<Option<Vec<u8>>, StoreError> {
+    match send(store_id, Action::Get(key.to_string()))? {
+        ResponseData::Get { data } => Ok(data),
+        other => Err(unexpected("Get", &other)),
+    }
+}
+
+/// Writes a value and returns the key the store actor reports for it, if any.
+pub fn put(store_id: &str, value: Vec<u8>) -> Result<Option<String>, StoreError> {
+    match send(store_id, Action::Put(value))? {
+        ResponseData::Put { data } => Ok(data),
+        other => Err(unexpected("Put", &other)),
+    }
+}
+
+/// Checks that `store_id` answers at all. A cheap `Get` is sent; a store that
+/// rejects it is still reachable, so only transport failures are errors.
+pub fn ping(store_id: &str) -> Result<(), StoreError> {
+    match send(store_id, Action::Get(String::new())) {
+        Ok(_) | Err(StoreError::Rejected(_)) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
@@ -0,0 +1,462 @@
+//! A small JSONPath / jq-subset evaluator over `serde_json::Value`.
+//!
+//! Supported JSONPath: `$`, `.name`, `['name']`, `[n]`, `[*]`, `.*`, `..name`
+//! and filters such as `[?(@.status == 'failed')]`.
+//!
+//! Supported jq: `.name`, `."name"`, `.[n]`, `.[]`, `..` and pipes into
+//! `select(.status == "failed")`.
+//!
+//! Missing fields produce no result rather than `null`, so an expression
+//! matches an entry exactly when it yields at least one value.
+
+use serde_json::Value;
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum PathSeg {
+    Field(String),
+    Index(i64),
+}
+
+/// `path` alone tests for a truthy value; `path op literal` compares.
+#[derive(Debug, Clone, PartialEq)]
+struct Condition {
+    path: Vec<PathSeg>,
+    comparison: Option<(CmpOp, Value)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Step {
+    Field(String),
+    Index(i64),
+    Wildcard,
+    /// `..name`, or every node including the current one for bare `..`.
+    Descendants(Option<String>),
+    /// JSONPath `[?(...)]`: keeps the children that satisfy the condition.
+    Filter(Condition),
+    /// jq `select(...)`: keeps the current node if it satisfies the condition.
+    Select(Condition),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    steps: Vec<Step>,
+}
+
+/// Splits on `separator` outside of quotes, brackets and parentheses.
+fn split_top_level(input: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut quote: Option<char> = None;
+    let mut start = 0;
+    for (i, c) in input.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '\'' | '"' => quote = Some(c),
+                '[' | '(' => depth += 1,
+                ']' | ')' => depth -= 1,
+                c if c == separator && depth == 0 => {
+                    parts.push(&input[start..i]);
+                    start = i + c.len_utf8();
+                }
+                _ => {}
+            },
+        }
+    }
+    parts.push(&input[start..]);
+    parts
+}
+
+fn unquote(input: &str) -> Option<String> {
+    let input = input.trim();
+    let quote = input.chars().next()?;
+    if (quote != '\'' && quote != '"') || input.len() < 2 || !input.ends_with(quote) {
+        return None;
+    }
+    Some(input[1..input.len() - 1].to_string())
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-' || c == '$'
+}
+
+/// Finds the index of the `]` closing the `[` at `open`.
+fn closing_bracket(input: &str, open: usize) -> Result<usize, String> {
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    for (i, c) in input[open..].char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '\'' | '"' => quote = Some(c),
+                '[' => depth += 1,
+                ']' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(open + i);
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+    Err(format!("unclosed '[' in '{}'", input))
+}
+
+fn parse_bracket(inner: &str) -> Result<Step, String> {
+    let inner = inner.trim();
+    if inner.is_empty() || inner == "*" {
+        return Ok(Step::Wildcard);
+    }
+    if let Some(name) = unquote(inner) {
+        return Ok(Step::Field(name));
+    }
+    if let Ok(index) = inner.parse::<i64>() {
+        return Ok(Step::Index(index));
+    }
+    if let Some(filter) = inner.strip_prefix('?') {
+        let filter = filter.trim();
+        let condition = filter
+            .strip_prefix('(')
+            .and_then(|f| f.strip_suffix(')'))
+            .ok_or_else(|| format!("expected ?(...) filter, found '{}'", inner))?;
+        return Ok(Step::Filter(parse_condition(condition)?));
+    }
+    Err(format!("unsupported selector '[{}]'", inner))
+}
+
+/// Parses a path such as `$.a['b'][0]`, `.a.b[]` or `@.status`.
+fn parse_path(input: &str, steps: &mut Vec<Step>) -> Result<(), String> {
+    let input = input.trim();
+    let mut i = match input.chars().next() {
+        Some('$') | Some('@') => 1,
+        _ => 0,
+    };
+    while i < input.len() {
+        let rest = &input[i..];
+        if let Some(after) = rest.strip_prefix("..") {
+            i += 2;
+            let name: String = after.chars().take_while(|c| is_ident_char(*c)).collect();
+            if name.is_empty() {
+                steps.push(Step::Descendants(None));
+                if after.starts_with('*') {
+                    steps.push(Step::Wildcard);
+                    i += 1;
+                }
+            } else {
+                i += name.len();
+                steps.push(Step::Descendants(Some(name)));
+            }
+        } else if let Some(after) = rest.strip_prefix('.') {
+            i += 1;
+            if after.is_empty() || after.starts_with('[') {
+                continue;
+            }
+            if after.starts_with('*') {
+                steps.push(Step::Wildcard);
+                i += 1;
+            } else if let Some(quoted) = after.strip_prefix('"') {
+                let end = quoted
+                    .find('"')
+                    .ok_or_else(|| format!("unclosed quote in '{}'", input))?;
+                steps.push(Step::Field(quoted[..end].to_string()));
+                i += end + 2;
+            } else {
+                let name: String = after.chars().take_while(|c| is_ident_char(*c)).collect();
+                if name.is_empty() {
+                    return Err(format!("expected a field name after '.' in '{}'", input));
+                }
+                i += name.len();
+                steps.push(Step::Field(name));
+            }
+        } else if rest.starts_with('[') {
+            let close = closing_bracket(input, i)?;
+            steps.push(parse_bracket(&input[i + 1..close])?);
+            i = close + 1;
+        } else {
+            return Err(format!("unexpected '{}' in '{}'", rest, input));
+        }
+    }
+    Ok(())
+}
+
+fn parse_literal(input: &str) -> Result<Value, String> {
+    if let Some(text) = unquote(input) {
+        return Ok(Value::String(text));
+    }
+    serde_json::from_str(input.trim()).map_err(|_| format!("invalid literal '{}'", input.trim()))
+}
+
+fn parse_condition(input: &str) -> Result<Condition, String> {
+    const OPS: [(&str, CmpOp); 6] = [
+        ("==", CmpOp::Eq),
+        ("!=", CmpOp::Ne),
+        ("<=", CmpOp::Le),
+        (">=", CmpOp::Ge),
+        ("<", CmpOp::Lt),
+        (">", CmpOp::Gt),
+    ];
+
+    let mut quote: Option<char> = None;
+    let mut split = None;
+    for (i, c) in input.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '\'' || c == '"' => quote = Some(c),
+            None => {
+                if let Some((token, op)) = OPS.iter().find(|(t, _)| input[i..].starts_with(t)) {
+                    split = Some((i, token.len(), *op));
+                    break;
+                }
+            }
+        }
+    }
+
+    let (path_expr, comparison) = match split {
+        Some((at, len, op)) => (&input[..at], Some((op, parse_literal(&input[at + len..])?))),
+        None => (input, None),
+    };
+
+    let mut steps = Vec::new();
+    parse_path(path_expr, &mut steps)?;
+    let path = steps
+        .into_iter()
+        .map(|step| match step {
+            Step::Field(name) => Ok(PathSeg::Field(name)),
+            Step::Index(index) => Ok(PathSeg::Index(index)),
+            _ => Err(format!(
+                "only plain paths are allowed in conditions: '{}'",
+                input
+            )),
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Condition { path, comparison })
+}
+
+impl Query {
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let expression = expression.trim();
+        if expression.is_empty() {
+            return Err("empty expression".to_string());
+        }
+        let mut steps = Vec::new();
+        for segment in split_top_level(expression, '|') {
+            let segment = segment.trim();
+            match segment
+                .strip_prefix("select(")
+                .and_then(|s| s.strip_suffix(')'))
+            {
+                Some(condition) => steps.push(Step::Select(parse_condition(condition)?)),
+                None => parse_path(segment, &mut steps)?,
+            }
+        }
+        Ok(Query { steps })
+    }
+
+    /// Every value the expression selects from `root`.
+    pub fn evaluate(&self, root: &Value) -> Vec<Value> {
+        let mut nodes = vec![root];
+        for step in &self.steps {
+            nodes = nodes
+                .into_iter()
+                .flat_map(|node| apply(step, node))
+                .collect();
+        }
+        nodes.into_iter().cloned().collect()
+    }
+}
+
+fn index_into(items: &[Value], index: i64) -> Option<&Value> {
+    let index = if index < 0 {
+        items.len().checked_sub(index.unsigned_abs() as usize)?
+    } else {
+        index as usize
+    };
+    items.get(index)
+}
+
+fn children(node: &Value) -> Vec<&Value> {
+    match node {
+        Value::Array(items) => items.iter().collect(),
+        Value::Object(map) => map.values().collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn descendants<'a>(node: &'a Value, out: &mut Vec<&'a Value>) {
+    out.push(node);
+    for child in children(node) {
+        descendants(child, out);
+    }
+}
+
+fn apply<'a>(step: &Step, node: &'a Value) -> Vec<&'a Value> {
+    match step {
+        Step::Field(name) => node.get(name).into_iter().collect(),
+        Step::Index(index) => match node {
+            Value::Array(items) => index_into(items, *index).into_iter().collect(),
+            _ => Vec::new(),
+        },
+        Step::Wildcard => children(node),
+        Step::Descendants(name) => {
+            let mut all = Vec::new();
+            descendants(node, &mut all);
+            match name {
+                Some(name) => all.into_iter().filter_map(|n| n.get(name)).collect(),
+                None => all,
+            }
+        }
+        Step::Filter(condition) => children(node)
+            .into_iter()
+            .filter(|child| condition.holds(child))
+            .collect(),
+        Step::Select(condition) => {
+            if condition.holds(node) {
+                vec![node]
+            } else {
+                Vec::new()
+            }
+        }
+    }
+}
+
+impl Condition {
+    fn holds(&self, node: &Value) -> bool {
+        let mut current = Some(node);
+        for seg in &self.path {
+            current = current.and_then(|value| match (seg, value) {
+                (PathSeg::Field(name), _) => value.get(name),
+                (PathSeg::Index(index), Value::Array(items)) => index_into(items, *index),
+                _ => None,
+            });
+        }
+        match (&self.comparison, current) {
+            (None, Some(value)) => !matches!(value, Value::Null | Value::Bool(false)),
+            (None, None) => false,
+            (Some((op, literal)), value) => compare(value.unwrap_or(&Value::Null), *op, literal),
+        }
+    }
+}
+
+fn compare(left: &Value, op: CmpOp, right: &Value) -> bool {
+    let ordering = match (left, right) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64().partial_cmp(&b.as_f64()),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ => (left == right).then_some(Ordering::Equal),
+    };
+    match op {
+        CmpOp::Eq => ordering == Some(Ordering::Equal),
+        CmpOp::Ne => ordering != Some(Ordering::Equal),
+        CmpOp::Lt => ordering == Some(Ordering::Less),
+        CmpOp::Le => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+        CmpOp::Gt => ordering == Some(Ordering::Greater),
+        CmpOp::Ge => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn doc() -> Value {
+        json!({
+            "name": "batch",
+            "items": [
+                { "id": 1, "status": "failed", "tags": ["a"] },
+                { "id": 2, "status": "ok", "retry": true },
+                { "id": 3, "status": "failed", "meta": { "id": 30 } }
+            ],
+            "odd key": 7
+        })
+    }
+
+    fn eval(expression: &str) -> Vec<Value> {
+        Query::parse(expression).unwrap().evaluate(&doc())
+    }
+
+    #[test]
+    fn parses_jsonpath_steps() {
+        let query = Query::parse("$.items[0]['odd key'].*").unwrap();
+        assert_eq!(
+            query.steps,
+            vec![
+                Step::Field("items".to_string()),
+                Step::Index(0),
+                Step::Field("odd key".to_string()),
+                Step::Wildcard,
+            ]
+        );
+        assert_eq!(
+            Query::parse("$..id").unwrap().steps,
+            vec![Step::Descendants(Some("id".to_string()))]
+        );
+    }
+
+    #[test]
+    fn evaluates_jsonpath() {
+        assert_eq!(eval("$.name"), [json!("batch")]);
+        assert_eq!(eval("$['odd key']"), [json!(7)]);
+        assert_eq!(eval("$.items[-1].id"), [json!(3)]);
+        assert_eq!(eval("$.items[*].id"), [json!(1), json!(2), json!(3)]);
+        assert_eq!(eval("$..id"), [json!(1), json!(2), json!(3), json!(30)]);
+        assert_eq!(
+            eval("$.items[?(@.status == 'failed')].id"),
+            [json!(1), json!(3)]
+        );
+        assert_eq!(eval("$.items[?(@.id >= 2)].id"), [json!(2), json!(3)]);
+        assert_eq!(eval("$.items[?(@.retry)].id"), [json!(2)]);
+        assert_eq!(eval("$.items[?(@.tags[0] == 'a')].id"), [json!(1)]);
+    }
+
+    #[test]
+    fn evaluates_jq() {
+        assert_eq!(eval(".name"), [json!("batch")]);
+        assert_eq!(eval(".\"odd key\""), [json!(7)]);
+        assert_eq!(eval(".items[1].status"), [json!("ok")]);
+        assert_eq!(
+            eval(".items[] | select(.status == \"failed\") | .id"),
+            [json!(1), json!(3)]
+        );
+        assert_eq!(
+            eval(".items[] | select(.status != \"failed\") | .id"),
+            [json!(2)]
+        );
+        assert_eq!(eval("select(.name == \"other\")"), Vec::<Value>::new());
+    }
+
+    #[test]
+    fn missing_fields_yield_nothing() {
+        assert!(eval("$.missing").is_empty());
+        assert!(eval("$.items[9]").is_empty());
+        assert!(eval("$.name[0]").is_empty());
+        assert!(eval("$.items[?(@.missing == 1)]").is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        for expression in [
+            "",
+            "   ",
+            "$.items[",
+            "$.items[?(@..x == 1)]",
+            "select(.a == nope)",
+        ] {
+            assert!(Query::parse(expression).is_err(), "{}", expression);
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use crate::bindings::exports::ntwk::theater::http_server::HttpResponse;
+use serde_json::{json, Value};
+
+pub fn json_response(status: u16, body: &Value) -> HttpResponse {
+    HttpResponse {
+        status,
+        headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+        body: Some(serde_json::to_vec(body).unwrap_or_default()),
+    }
+}
+
+/// `body` with `"status": "success"` added, for replies other than HTTP
+/// responses (actor messages).
+pub fn success_body(body: Value) -> Value {
+    let mut body = body;
+    if let Value::Object(map) = &mut body {
+        map.insert("status".to_string(), json!("success"));
+    }
+    body
+}
+
+pub fn success(body: Value) -> HttpResponse {
+    json_response(200, &success_body(body))
+}
+
+/// Machine-readable error code used when a handler doesn't pick a specific one.
+fn default_code(status: u16) -> &'static str {
+    match status {
+        400 => "bad_request",
+        403 => "forbidden",
+        404 => "not_found",
+        405 => "method_not_allowed",
+        422 => "unprocessable",
+        502 => "bad_gateway",
+        503 => "unavailable",
+        _ => "internal_error",
+    }
+}
+
+/// The error body sent by `error_with_code`.
+pub fn error_body(code: &str, message: &str) -> Value {
+    json!({
+        "status": "error",
+        "code": code,
+        "message": message
+    })
+}
+
+pub fn error_with_code(status: u16, code: &str, message: &str) -> HttpResponse {
+    json_response(status, &error_body(code, message))
+}
+
+pub fn error(status: u16, message: &str) -> HttpResponse {
+    error_with_code(status, default_code(status), message)
+}
+
+pub fn not_found() -> HttpResponse {
+    error(404, "Not Found")
+}
@@ -0,0 +1,271 @@
+//! Replays a dump into a store through `Action::Put`.
+//!
+//! `Put` carries only the value; the store actor picks the key. A dumped
+//! entry is only restored when the store reports the dumped key for it. A
+//! key that already holds a different value cannot be replaced this way, so
+//! such keys are conflicts, handled according to the restore's `Policy`.
+
+use crate::bindings::exports::ntwk::theater::http_server::HttpResponse;
+use crate::bindings::ntwk::theater::runtime::log;
+use crate::listing::Entry;
+use crate::router::RequestContext;
+use crate::{dump, response, snapshots, State};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+
+/// What to do with a key that already holds a different value.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Policy {
+    /// Put the value anyway; the store reports where it ended up.
+    #[default]
+    #[serde(alias = "overwrite")]
+    Write,
+    Skip,
+    /// Refuse the whole restore, before anything is written.
+    Fail,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RestoreRequest {
+    /// `snapshot:<id>` or `file:<path>`.
+    pub source: String,
+    #[serde(default)]
+    pub dry_run: bool,
+    #[serde(default)]
+    pub policy: Policy,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    /// Would be written (dry run).
+    Pending,
+    /// Written, and the store reported the dumped key.
+    Restored,
+    /// Written, but the store chose another key; the dumped key is untouched.
+    StoredUnderDifferentKey,
+    /// Written, but the store reported no key, so the dumped key may not
+    /// hold the value.
+    NotRestored,
+    Skipped,
+    Failed,
+}
+
+#[derive(Serialize, Debug)]
+pub struct KeyReport {
+    pub key: String,
+    pub outcome: Outcome,
+    /// The key already holds a different value.
+    pub conflict: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// The key the store reported for the write, when it differs from `key`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stored_key: Option<String>,
+}
+
+impl KeyReport {
+    /// Records the key the store reported for the write of this entry.
+    fn written(&mut self, stored: Option<String>) {
+        self.outcome = match stored.as_deref() {
+            Some(stored) if stored == self.key => Outcome::Restored,
+            Some(_) => Outcome::StoredUnderDifferentKey,
+            None => Outcome::NotRestored,
+        };
+        self.stored_key = stored.filter(|k| *k != self.key);
+    }
+}
+
+/// Reads the dump named by `source`: `snapshot:<id>` or `file:<path>`.
+pub fn load_source(state: &State, source: &str) -> Result<Vec<Entry>, dump::DumpError> {
+    if let Some(id) = source.strip_prefix("snapshot:") {
+        return dump::read(&snapshots::entries_path(state, id)?);
+    }
+    match source.strip_prefix("file:") {
+        Some(path) => dump::read(path),
+        None => Err(dump::DumpError::InvalidPath(source.to_string())),
+    }
+}
+
+/// Decides what happens to each dumped entry given the store's current
+/// contents, without writing anything: entries to write are `Pending`.
+pub fn plan(
+    entries: &[Entry],
+    current: &HashMap<String, Vec<u8>>,
+    policy: Policy,
+) -> Vec<KeyReport> {
+    entries
+        .iter()
+        .map(|entry| {
+            let (outcome, conflict, reason) = match current.get(&entry.key) {
+                None => (Outcome::Pending, false, None),
+                Some(value) if *value == entry.value => {
+                    (Outcome::Skipped, false, Some("identical value"))
+                }
+                Some(_) if policy == Policy::Skip => {
+                    (Outcome::Skipped, true, Some("key holds a different value"))
+                }
+                Some(_) => (Outcome::Pending, true, Some("key holds a different value")),
+            };
+            KeyReport {
+                key: entry.key.clone(),
+                outcome,
+                conflict,
+                reason: reason.map(str::to_string),
+                stored_key: None,
+            }
+        })
+        .collect()
+}
+
+fn summary(reports: &[KeyReport]) -> serde_json::Value {
+    let count = |outcome| reports.iter().filter(|r| r.outcome == outcome).count();
+    json!({
+        "pending": count(Outcome::Pending),
+        "restored": count(Outcome::Restored),
+        "stored_under_different_key": count(Outcome::StoredUnderDifferentKey),
+        "not_restored": count(Outcome::NotRestored),
+        "skipped": count(Outcome::Skipped),
+        "failed": count(Outcome::Failed),
+        "conflicts": reports.iter().filter(|r| r.conflict).count(),
+    })
+}
+
+/// `POST /api/restore` with a `RestoreRequest` body.
+pub fn restore(req: &RequestContext, state: &mut State) -> HttpResponse {
+    let request: RestoreRequest = match serde_json::from_slice(req.body) {
+        Ok(request) => request,
+        Err(e) => return response::error(400, &format!("Invalid restore request: {}", e)),
+    };
+    if state.config.read_only && !request.dry_run {
+        return response::error(403, "The visualizer is in read-only mode");
+    }
+
+    let entries = match load_source(state, &request.source) {
+        Ok(entries) => entries,
+        Err(e) => return e.into_response(),
+    };
+    let current: HashMap<String, Vec<u8>> = match state.reload_entries(req.store_id) {
+        Ok(current) => current.into_iter().map(|e| (e.key, e.value)).collect(),
+        Err(e) => return e.into_response(),
+    };
+    let mut reports = plan(&entries, &current, request.policy);
+
+    if request.policy == Policy::Fail {
+        let conflicts: Vec<&str> = reports
+            .iter()
+            .filter(|r| r.conflict)
+            .map(|r| r.key.as_str())
+            .collect();
+        if !conflicts.is_empty() {
+            let body = json!({
+                "status": "error",
+                "code": "restore_conflict",
+                "message": format!("{} keys already hold different values", conflicts.len()),
+                "dry_run": request.dry_run,
+                "conflicts": conflicts,
+            });
+            return response::json_response(409, &body);
+        }
+    }
+
+    if !request.dry_run {
+        for (report, entry) in reports.iter_mut().zip(&entries) {
+            if report.outcome == Outcome::Skipped {
+                continue;
+            }
+            match state.put_value(req.store_id, entry.value.clone()) {
+                Ok(stored) => report.written(stored),
+                Err(e) => {
+                    report.outcome = Outcome::Failed;
+                    report.reason = Some(e.to_string());
+                }
+            }
+        }
+        log(&format!(
+            "Restored {} into {}: {}",
+            request.source,
+            req.store_id,
+            summary(&reports)
+        ));
+    }
+
+    response::success(json!({
+        "source": request.source,
+        "dry_run": request.dry_run,
+        "policy": request.policy,
+        "summary": summary(&reports),
+        "keys": reports,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::entries;
+
+    fn current(pairs: &[(&str, &str)]) -> HashMap<String, Vec<u8>> {
+        entries(pairs)
+            .into_iter()
+            .map(|e| (e.key, e.value))
+            .collect()
+    }
+
+    fn outcomes(reports: &[KeyReport]) -> Vec<(&str, Outcome, bool)> {
+        reports
+            .iter()
+            .map(|r| (r.key.as_str(), r.outcome, r.conflict))
+            .collect()
+    }
+
+    #[test]
+    fn plan_flags_conflicts() {
+        let dump = entries(&[("new", "1"), ("same", "2"), ("other", "3")]);
+        let store = current(&[("same", "2"), ("other", "old")]);
+
+        let write = plan(&dump, &store, Policy::Write);
+        assert_eq!(
+            outcomes(&write),
+            [
+                ("new", Outcome::Pending, false),
+                ("same", Outcome::Skipped, false),
+                ("other", Outcome::Pending, true),
+            ]
+        );
+        let skip = plan(&dump, &store, Policy::Skip);
+        assert_eq!(skip[2].outcome, Outcome::Skipped);
+        assert!(skip[2].conflict);
+    }
+
+    #[test]
+    fn writes_are_checked_against_the_stored_key() {
+        let mut reports = plan(
+            &entries(&[("a", "1"), ("b", "2"), ("c", "3")]),
+            &current(&[]),
+            Policy::Write,
+        );
+        reports[0].written(Some("a".to_string()));
+        reports[1].written(Some("sha-of-2".to_string()));
+        reports[2].written(None);
+        assert_eq!(
+            outcomes(&reports),
+            [
+                ("a", Outcome::Restored, false),
+                ("b", Outcome::StoredUnderDifferentKey, false),
+                ("c", Outcome::NotRestored, false),
+            ]
+        );
+        assert_eq!(reports[0].stored_key, None);
+        assert_eq!(reports[1].stored_key.as_deref(), Some("sha-of-2"));
+        assert_eq!(summary(&reports)["restored"], 1);
+    }
+
+    #[test]
+    fn overwrite_is_accepted_as_write() {
+        let policy: Policy = serde_json::from_str(r#""overwrite""#).unwrap();
+        assert_eq!(policy, Policy::Write);
+    }
+}
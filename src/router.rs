@@ -0,0 +1,335 @@
+//! Minimal HTTP router: path templates with `:name` segments, percent-decoding,
+//! query-string parsing and method-not-allowed detection.
+
+use crate::bindings::exports::ntwk::theater::http_server::HttpResponse;
+use crate::response;
+use std::str::FromStr;
+
+/// A parameter that was missing or could not be parsed into the requested type.
+#[derive(Debug)]
+pub struct ParamError {
+    pub name: String,
+    pub message: String,
+}
+
+impl ParamError {
+    pub fn into_response(self) -> HttpResponse {
+        response::error_with_code(
+            400,
+            "invalid_parameter",
+            &format!("Invalid parameter '{}': {}", self.name, self.message),
+        )
+    }
+}
+
+fn parse_value<T: FromStr>(name: &str, value: &str) -> Result<T, ParamError>
+where
+    T::Err: std::fmt::Display,
+{
+    value.parse().map_err(|e: T::Err| ParamError {
+        name: name.to_string(),
+        message: e.to_string(),
+    })
+}
+
+/// Ordered, decoded name/value pairs (path parameters or query string).
+#[derive(Debug, Default, Clone)]
+pub struct Params(Vec<(String, String)>);
+
+impl Params {
+    /// Parses an `a=1&b=two` query string. Keys without `=` get an empty value.
+    pub fn from_query(query: &str) -> Self {
+        Params(
+            query
+                .split('&')
+                .filter(|pair| !pair.is_empty())
+                .map(|pair| {
+                    let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+                    (decode_query_component(name), decode_query_component(value))
+                })
+                .collect(),
+        )
+    }
+
+    /// Builds parameters from already decoded pairs.
+    pub fn from_pairs(pairs: impl IntoIterator<Item = (String, String)>) -> Self {
+        Params(pairs.into_iter().collect())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns the parameter parsed as `T`, or `None` if it is absent.
+    pub fn parse<T: FromStr>(&self, name: &str) -> Result<Option<T>, ParamError>
+    where
+        T::Err: std::fmt::Display,
+    {
+        self.get(name).map(|v| parse_value(name, v)).transpose()
+    }
+}
+
+/// Decodes `%XX` escapes. Invalid escapes are kept verbatim and invalid UTF-8
+/// is replaced, so decoding never fails.
+pub fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && i + 2 < bytes.len()
+            && bytes[i + 1].is_ascii_hexdigit()
+            && bytes[i + 2].is_ascii_hexdigit()
+        {
+            out.push((hex_value(bytes[i + 1]) << 4) | hex_value(bytes[i + 2]));
+            i += 3;
+            continue;
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn hex_value(digit: u8) -> u8 {
+    match digit {
+        b'0'..=b'9' => digit - b'0',
+        b'a'..=b'f' => digit - b'a' + 10,
+        _ => digit - b'A' + 10,
+    }
+}
+
+fn decode_query_component(input: &str) -> String {
+    percent_decode(&input.replace('+', " "))
+}
+
+#[derive(Debug, PartialEq)]
+enum Segment {
+    Literal(String),
+    Param(String),
+}
+
+struct Route<H> {
+    method: &'static str,
+    segments: Vec<Segment>,
+    handler: H,
+}
+
+impl<H> Route<H> {
+    fn matches(&self, path: &[String]) -> Option<Params> {
+        if self.segments.len() != path.len() {
+            return None;
+        }
+        let mut params = Vec::new();
+        for (segment, part) in self.segments.iter().zip(path) {
+            match segment {
+                Segment::Literal(literal) if literal == part => {}
+                Segment::Literal(_) => return None,
+                Segment::Param(name) => params.push((name.clone(), part.clone())),
+            }
+        }
+        Some(Params(params))
+    }
+}
+
+/// The request as seen by a route handler.
+pub struct RequestContext<'a> {
+    pub params: Params,
+    pub query: Params,
+    pub headers: &'a [(String, String)],
+    pub body: &'a [u8],
+    /// Actor id of the key-value store the request is addressed to.
+    pub store_id: &'a str,
+}
+
+impl RequestContext<'_> {
+    /// A path parameter declared in the matched route template.
+    pub fn param(&self, name: &str) -> &str {
+        self.params.get(name).unwrap_or_default()
+    }
+
+    /// Case-insensitive header lookup.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        header(self.headers, name)
+    }
+}
+
+/// Case-insensitive lookup in a raw header list.
+pub fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, v)| v.as_str())
+}
+
+pub enum Dispatch<H> {
+    Matched { handler: H, params: Params },
+    MethodNotAllowed { allow: Vec<&'static str> },
+    NotFound,
+}
+
+pub struct Router<H> {
+    routes: Vec<Route<H>>,
+}
+
+impl<H: Copy> Router<H> {
+    pub fn new() -> Self {
+        Router { routes: Vec::new() }
+    }
+
+    /// Registers `handler` for `method` on a template such as `/api/content/:hash`.
+    pub fn route(mut self, method: &'static str, template: &str, handler: H) -> Self {
+        let segments = split_path(template)
+            .into_iter()
+            .map(|s| match s.strip_prefix(':') {
+                Some(name) => Segment::Param(name.to_string()),
+                None => Segment::Literal(s),
+            })
+            .collect();
+        self.routes.push(Route {
+            method,
+            segments,
+            handler,
+        });
+        self
+    }
+
+    pub fn get(self, template: &str, handler: H) -> Self {
+        self.route("GET", template, handler)
+    }
+
+    pub fn post(self, template: &str, handler: H) -> Self {
+        self.route("POST", template, handler)
+    }
+
+    pub fn put(self, template: &str, handler: H) -> Self {
+        self.route("PUT", template, handler)
+    }
+
+    pub fn patch(self, template: &str, handler: H) -> Self {
+        self.route("PATCH", template, handler)
+    }
+
+    pub fn delete(self, template: &str, handler: H) -> Self {
+        self.route("DELETE", template, handler)
+    }
+
+    /// Finds the handler for an already split and decoded path.
+    pub fn dispatch(&self, method: &str, path: &[String]) -> Dispatch<H> {
+        let mut allow = Vec::new();
+        for route in &self.routes {
+            if let Some(params) = route.matches(path) {
+                if route.method.eq_ignore_ascii_case(method) {
+                    return Dispatch::Matched {
+                        handler: route.handler,
+                        params,
+                    };
+                }
+                if !allow.contains(&route.method) {
+                    allow.push(route.method);
+                }
+            }
+        }
+        if allow.is_empty() {
+            Dispatch::NotFound
+        } else {
+            Dispatch::MethodNotAllowed { allow }
+        }
+    }
+}
+
+/// Splits a URI into its percent-decoded path segments and its query string.
+pub fn split_uri(uri: &str) -> (Vec<String>, Params) {
+    let uri = uri.split('#').next().unwrap_or_default();
+    let (path, query) = uri.split_once('?').unwrap_or((uri, ""));
+    let segments = split_path(path).iter().map(|s| percent_decode(s)).collect();
+    (segments, Params::from_query(query))
+}
+
+fn split_path(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+pub fn method_not_allowed(allow: &[&str]) -> HttpResponse {
+    let mut response = response::error(405, "Method Not Allowed");
+    response
+        .headers
+        .push(("Allow".to_string(), allow.join(", ")));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::path;
+
+    fn router() -> Router<u8> {
+        Router::new()
+            .get("/api/entries/:key", 1)
+            .put("/api/entries/:key", 2)
+            .get("/api/entries", 3)
+    }
+
+    #[test]
+    fn percent_decode_handles_escapes() {
+        assert_eq!(percent_decode("a%20b%2Fc"), "a b/c");
+        assert_eq!(percent_decode("%e2%9c%93"), "\u{2713}");
+        // Invalid or truncated escapes are kept as they are.
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz%4"), "%zz%4");
+        assert_eq!(percent_decode("%ff"), "\u{fffd}");
+    }
+
+    #[test]
+    fn query_strings_decode_plus_and_empty_values() {
+        let query = Params::from_query("q=a+b%2Bc&flag&n=5&&bad=x");
+        assert_eq!(query.get("q"), Some("a b+c"));
+        assert_eq!(query.get("flag"), Some(""));
+        assert_eq!(query.parse::<u32>("n").unwrap(), Some(5));
+        assert_eq!(query.parse::<u32>("missing").unwrap(), None);
+        assert_eq!(query.parse::<u32>("bad").unwrap_err().name, "bad");
+    }
+
+    #[test]
+    fn split_uri_decodes_segments() {
+        let (segments, query) = split_uri("/api/entries/a%2Fb/?x=1#frag");
+        assert_eq!(segments, path(&["api", "entries", "a/b"]));
+        assert_eq!(query.get("x"), Some("1"));
+    }
+
+    #[test]
+    fn dispatch_matches_templates() {
+        match router().dispatch("get", &path(&["api", "entries", "k"])) {
+            Dispatch::Matched { handler, params } => {
+                assert_eq!(handler, 1);
+                assert_eq!(params.get("key"), Some("k"));
+            }
+            _ => panic!("expected a match"),
+        }
+        assert!(matches!(
+            router().dispatch("GET", &path(&["api", "entries"])),
+            Dispatch::Matched { handler: 3, .. }
+        ));
+        assert!(matches!(
+            router().dispatch("GET", &path(&["api", "other"])),
+            Dispatch::NotFound
+        ));
+    }
+
+    #[test]
+    fn wrong_method_lists_allowed_ones() {
+        match router().dispatch("DELETE", &path(&["api", "entries", "k"])) {
+            Dispatch::MethodNotAllowed { allow } => assert_eq!(allow, ["GET", "PUT"]),
+            _ => panic!("expected method not allowed"),
+        }
+        let response = method_not_allowed(&["GET", "PUT"]);
+        assert_eq!(response.status, 405);
+        assert_eq!(header(&response.headers, "allow"), Some("GET, PUT"));
+    }
+}
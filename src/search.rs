@@ -0,0 +1,182 @@
+//! Server-side filtering of store entries.
+
+use crate::content_type;
+use crate::listing::Entry;
+use crate::router::{ParamError, Params};
+use regex_lite::Regex;
+
+/// Conjunction of all filters given on the query string. Absent filters match
+/// everything.
+#[derive(Debug, Default)]
+pub struct SearchFilter {
+    prefix: Option<String>,
+    /// Lowercased; matched case-insensitively anywhere in the key.
+    key_contains: Option<String>,
+    key_patterns: Vec<Regex>,
+    contains: Option<String>,
+    min_size: Option<usize>,
+    max_size: Option<usize>,
+    content_type: Option<String>,
+}
+
+fn invalid(name: &str, message: impl ToString) -> ParamError {
+    ParamError {
+        name: name.to_string(),
+        message: message.to_string(),
+    }
+}
+
+/// Translates a glob (`*`, `?`, `[...]`) into an anchored regular expression.
+fn glob_to_regex(glob: &str) -> String {
+    let mut pattern = String::from("^");
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            '[' => {
+                pattern.push('[');
+                if chars.peek() == Some(&'!') {
+                    chars.next();
+                    pattern.push('^');
+                }
+                for c in chars.by_ref() {
+                    if c == ']' {
+                        break;
+                    }
+                    if c == '\\' || c == '[' || c == '^' {
+                        pattern.push('\\');
+                    }
+                    pattern.push(c);
+                }
+                pattern.push(']');
+            }
+            c => pattern.push_str(&regex_lite::escape(&c.to_string())),
+        }
+    }
+    pattern.push('$');
+    pattern
+}
+
+impl SearchFilter {
+    /// Reads `prefix`, `key_contains`, `glob`, `regex`, `contains`,
+    /// `min_size`, `max_size` and `type` from a query string.
+    pub fn from_query(query: &Params) -> Result<Self, ParamError> {
+        let mut key_patterns = Vec::new();
+        if let Some(glob) = query.get("glob").filter(|g| !g.is_empty()) {
+            key_patterns.push(Regex::new(&glob_to_regex(glob)).map_err(|e| invalid("glob", e))?);
+        }
+        if let Some(regex) = query.get("regex").filter(|r| !r.is_empty()) {
+            key_patterns.push(Regex::new(regex).map_err(|e| invalid("regex", e))?);
+        }
+
+        let non_empty = |name: &str| {
+            query
+                .get(name)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+        Ok(SearchFilter {
+            prefix: non_empty("prefix"),
+            key_contains: non_empty("key_contains").map(|text| text.to_lowercase()),
+            key_patterns,
+            contains: non_empty("contains"),
+            min_size: query.parse("min_size")?,
+            max_size: query.parse("max_size")?,
+            content_type: non_empty("type"),
+        })
+    }
+
+    pub fn matches(&self, entry: &Entry) -> bool {
+        let size = entry.value.len();
+        self.prefix
+            .as_ref()
+            .is_none_or(|prefix| entry.key.starts_with(prefix.as_str()))
+            && self
+                .key_contains
+                .as_ref()
+                .is_none_or(|text| entry.key.to_lowercase().contains(text.as_str()))
+            && self.key_patterns.iter().all(|re| re.is_match(&entry.key))
+            && self.min_size.is_none_or(|min| size >= min)
+            && self.max_size.is_none_or(|max| size <= max)
+            && self.contains.as_ref().is_none_or(|needle| {
+                entry
+                    .value
+                    .windows(needle.len().max(1))
+                    .any(|window| window == needle.as_bytes())
+            })
+            && self
+                .content_type
+                .as_ref()
+                .is_none_or(|ty| content_type::detect(&entry.value).matches(ty))
+    }
+
+    pub fn apply(&self, entries: Vec<Entry>) -> Vec<Entry> {
+        entries.into_iter().filter(|e| self.matches(e)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::entry;
+
+    fn filter(query: &str) -> SearchFilter {
+        SearchFilter::from_query(&Params::from_query(query)).unwrap()
+    }
+
+    #[test]
+    fn key_contains_is_literal_and_case_insensitive() {
+        let f = filter("key_contains=User%5B1%5D%3F");
+        assert!(f.matches(&entry("admin:user[1]?", "")));
+        assert!(!f.matches(&entry("admin:user1", "")));
+        assert!(filter("key_contains=*").matches(&entry("a*b", "")));
+        assert!(!filter("key_contains=*").matches(&entry("ab", "")));
+    }
+
+    #[test]
+    fn globs_translate_to_anchored_patterns() {
+        assert_eq!(glob_to_regex("user:*"), "^user:.*$");
+        assert_eq!(glob_to_regex("a?c"), "^a.c$");
+        assert_eq!(glob_to_regex("[!ab]x"), "^[^ab]x$");
+        assert_eq!(glob_to_regex("a.b+"), "^a\\.b\\+$");
+
+        let f = filter("glob=job:%5B0-9%5D*");
+        assert!(f.matches(&entry("job:7:done", "")));
+        assert!(!f.matches(&entry("job:x", "")));
+        assert!(!f.matches(&entry("old-job:7", "")));
+    }
+
+    #[test]
+    fn regex_is_unanchored() {
+        let f = filter("regex=%5Cd%7B3%7D");
+        assert!(f.matches(&entry("order-123-x", "")));
+        assert!(!f.matches(&entry("order-12", "")));
+        let error = SearchFilter::from_query(&Params::from_query("regex=(")).unwrap_err();
+        assert_eq!(error.name, "regex");
+    }
+
+    #[test]
+    fn all_filters_must_match() {
+        let f = filter("prefix=user:&contains=admin&min_size=10&max_size=40&type=json");
+        assert!(f.matches(&entry("user:1", r#"{"role":"admin"}"#)));
+        assert!(!f.matches(&entry("team:1", r#"{"role":"admin"}"#)));
+        assert!(!f.matches(&entry("user:2", r#"{"role":"guest"}"#)));
+        assert!(!f.matches(&entry("user:3", "role admin")));
+        assert!(!f.matches(&entry(
+            "user:4",
+            r#"{"role":"admin","padding":"xxxxxxxxxxxx"}"#
+        )));
+        assert!(!f.matches(&entry("user:5", r#"["admin"]"#)));
+
+        assert!(filter("type=text").matches(&entry("k", "<p>hi</p>")));
+        assert!(filter("").matches(&entry("anything", "")));
+        assert!(SearchFilter::from_query(&Params::from_query("min_size=x")).is_err());
+    }
+
+    #[test]
+    fn empty_parameters_are_ignored() {
+        let f = filter("prefix=&glob=&regex=&contains=&key_contains=&type=");
+        assert!(f.matches(&entry("k", "v")));
+    }
+}
@@ -0,0 +1,304 @@
+//! Point-in-time dumps of a store, written through the `filesystem` handler.
+//!
+//! Each snapshot is a directory below `snapshots.dir` holding `entries.json`
+//! (a dump, see `dump.rs`) and `manifest.json` describing it.
+
+use crate::bindings::exports::ntwk::theater::http_server::HttpResponse;
+use crate::bindings::ntwk::theater::filesystem::{
+    create_dir, list_files, path_exists, read_file, write_file,
+};
+use crate::bindings::ntwk::theater::runtime::log;
+use crate::listing::Entry;
+use crate::protocol::StoreError;
+use crate::router::RequestContext;
+use crate::{dump, response, time, State};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha1::{Digest, Sha1};
+use std::fmt;
+
+const ENTRIES_FILE: &str = "entries.json";
+const MANIFEST_FILE: &str = "manifest.json";
+/// Snapshots with the same base id (taken in the same second) get a `-2`,
+/// `-3`, ... suffix, up to this many in total.
+const MAX_SAME_ID: usize = 100;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct SnapshotConfig {
+    /// Directory, relative to the filesystem handler's root.
+    pub dir: String,
+}
+
+impl Default for SnapshotConfig {
+    fn default() -> Self {
+        SnapshotConfig {
+            dir: "snapshots".to_string(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Manifest {
+    pub id: String,
+    /// Registry name of the store the snapshot was taken from.
+    pub store: String,
+    pub store_id: String,
+    /// RFC 3339 time supplied by the client, if any.
+    pub created_at: Option<String>,
+    pub entry_count: usize,
+    /// Sum of the value sizes in bytes.
+    pub total_size: usize,
+    /// SHA-1 of `entries.json`, hex-encoded.
+    pub sha1: String,
+    pub file: String,
+}
+
+fn error(status: u16, message: String) -> HttpResponse {
+    log(&message);
+    response::error_with_code(status, "snapshot_error", &message)
+}
+
+fn ensure_dir(path: &str) -> Result<(), String> {
+    match path_exists(path)? {
+        true => Ok(()),
+        false => create_dir(path),
+    }
+}
+
+fn sha1_hex(data: &[u8]) -> String {
+    format!("{:x}", Sha1::digest(data))
+}
+
+/// Name of the store in the registry whose actor id is `store_id`.
+fn store_name(state: &State, store_id: &str) -> String {
+    state
+        .config
+        .registry()
+        .into_iter()
+        .find(|(_, id)| id == store_id)
+        .map(|(name, _)| name)
+        .unwrap_or_default()
+}
+
+/// Why a snapshot could not be taken.
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// The store could not be listed.
+    Store(StoreError),
+    /// Encoding or writing the files failed.
+    Write(String),
+    /// Every id for this second is taken.
+    Exists(String),
+}
+
+impl SnapshotError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            SnapshotError::Store(e) => e.code(),
+            SnapshotError::Write(_) => "snapshot_error",
+            SnapshotError::Exists(_) => "snapshot_exists",
+        }
+    }
+
+    pub fn into_response(self) -> HttpResponse {
+        match self {
+            SnapshotError::Store(e) => e.into_response(),
+            SnapshotError::Write(message) => error(500, message),
+            SnapshotError::Exists(id) => response::error_with_code(
+                409,
+                "snapshot_exists",
+                &format!("Snapshot {} already exists", id),
+            ),
+        }
+    }
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::Store(e) => e.fmt(f),
+            SnapshotError::Write(message) => f.write_str(message),
+            SnapshotError::Exists(id) => write!(f, "Snapshot {} already exists", id),
+        }
+    }
+}
+
+/// The first of `base`, `base-2`, `base-3`, ... for which `exists` is false.
+fn unused_id(
+    base: &str,
+    exists: impl Fn(&str) -> Result<bool, String>,
+) -> Result<String, SnapshotError> {
+    for n in 1..=MAX_SAME_ID {
+        let id = match n {
+            1 => base.to_string(),
+            n => format!("{}-{}", base, n),
+        };
+        if !exists(&id).map_err(SnapshotError::Write)? {
+            return Ok(id);
+        }
+    }
+    Err(SnapshotError::Exists(base.to_string()))
+}
+
+/// Dumps the store `store_id` and writes a new snapshot of it. `created` is
+/// the caller's time in milliseconds since the epoch, if known.
+pub fn take(
+    state: &State,
+    store_id: &str,
+    created: Option<u64>,
+) -> Result<Manifest, SnapshotError> {
+    let entries: Vec<Entry> = state
+        .reload_entries(store_id)
+        .map_err(SnapshotError::Store)?;
+    let data = serde_json::to_string(&entries)
+        .map_err(|e| SnapshotError::Write(format!("Could not encode the snapshot: {}", e)))?;
+    let sha1 = sha1_hex(data.as_bytes());
+    let store = store_name(state, store_id);
+
+    // Without a client clock, the content hash names the snapshot.
+    let base = match created {
+        Some(ms) => format!("{}-{}", store, time::to_compact(ms)),
+        None => format!("{}-{}", store, &sha1[..12]),
+    };
+    let dir = state.config.snapshots.dir.trim_end_matches('/').to_string();
+    let id = unused_id(&base, |id| path_exists(&format!("{}/{}", dir, id)))?;
+    let path = format!("{}/{}", dir, id);
+
+    let manifest = Manifest {
+        id: id.clone(),
+        store,
+        store_id: store_id.to_string(),
+        created_at: created.map(time::to_rfc3339),
+        entry_count: entries.len(),
+        total_size: entries.iter().map(|e| e.value.len()).sum(),
+        sha1,
+        file: ENTRIES_FILE.to_string(),
+    };
+    let manifest_json = serde_json::to_string_pretty(&manifest).unwrap_or_default();
+
+    ensure_dir(&dir)
+        .and_then(|()| ensure_dir(&path))
+        .and_then(|()| write_file(&format!("{}/{}", path, ENTRIES_FILE), &data))
+        .and_then(|()| write_file(&format!("{}/{}", path, MANIFEST_FILE), &manifest_json))
+        .map_err(|e| SnapshotError::Write(format!("Could not write snapshot {}: {}", path, e)))?;
+
+    log(&format!(
+        "Wrote snapshot {} ({} entries)",
+        id, manifest.entry_count
+    ));
+    Ok(manifest)
+}
+
+/// `POST /api/snapshots`: dumps the store and writes a new snapshot.
+pub fn create(req: &RequestContext, state: &mut State) -> HttpResponse {
+    match take(state, req.store_id, time::client_time(req)) {
+        Ok(manifest) => response::success(json!({ "snapshot": manifest })),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Reads the manifest of snapshot `id`.
+pub fn manifest(state: &State, id: &str) -> Result<Manifest, String> {
+    let path = format!(
+        "{}/{}/{}",
+        state.config.snapshots.dir.trim_end_matches('/'),
+        id,
+        MANIFEST_FILE
+    );
+    let data = read_file(&path).map_err(|e| format!("{}: {}", path, e))?;
+    serde_json::from_slice(&data).map_err(|e| format!("{}: {}", path, e))
+}
+
+/// Path of the entries file of snapshot `id`, after checking the id.
+pub fn entries_path(state: &State, id: &str) -> Result<String, dump::DumpError> {
+    if id.contains(['/', '\\']) {
+        return Err(dump::DumpError::InvalidPath(id.to_string()));
+    }
+    let path = format!(
+        "{}/{}/{}",
+        state.config.snapshots.dir.trim_end_matches('/'),
+        id,
+        ENTRIES_FILE
+    );
+    dump::check_path(&path)?;
+    Ok(path)
+}
+
+/// `GET /api/snapshots`: the manifests of every snapshot, newest first when
+/// they carry a time.
+pub fn list(_: &RequestContext, state: &mut State) -> HttpResponse {
+    let dir = state.config.snapshots.dir.trim_end_matches('/').to_string();
+    let names = match path_exists(&dir) {
+        Ok(false) => Vec::new(),
+        Ok(true) => match list_files(&dir) {
+            Ok(names) => names,
+            Err(e) => return error(500, format!("Could not list {}: {}", dir, e)),
+        },
+        Err(e) => return error(500, format!("Could not list {}: {}", dir, e)),
+    };
+
+    let mut snapshots = Vec::new();
+    let mut invalid = Vec::new();
+    for name in names {
+        let id = name.rsplit('/').next().unwrap_or(&name).to_string();
+        match manifest(state, &id) {
+            Ok(manifest) => snapshots.push(manifest),
+            Err(e) => invalid.push(json!({ "id": id, "error": e })),
+        }
+    }
+    snapshots.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(a.id.cmp(&b.id)));
+
+    response::success(json!({ "snapshots": snapshots, "invalid": invalid }))
+}
+
+/// `GET /api/snapshots/:id`: the manifest, with the content hash re-checked.
+pub fn get(req: &RequestContext, state: &mut State) -> HttpResponse {
+    let id = req.param("id");
+    let path = match entries_path(state, id) {
+        Ok(path) => path,
+        Err(e) => return e.into_response(),
+    };
+    let manifest = match manifest(state, id) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            return response::error_with_code(
+                404,
+                "not_found",
+                &format!("No snapshot '{}': {}", id, e),
+            )
+        }
+    };
+    let verified = read_file(&path)
+        .map(|data| sha1_hex(&data) == manifest.sha1)
+        .unwrap_or(false);
+    response::success(json!({ "snapshot": manifest, "verified": verified }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unused_id_appends_a_suffix() {
+        let taken = ["s-20240501T123000Z", "s-20240501T123000Z-2"];
+        let exists = |id: &str| Ok(taken.contains(&id));
+        assert_eq!(unused_id("s-other", exists).unwrap(), "s-other");
+        assert_eq!(
+            unused_id("s-20240501T123000Z", exists).unwrap(),
+            "s-20240501T123000Z-3"
+        );
+    }
+
+    #[test]
+    fn unused_id_gives_up_or_fails() {
+        assert!(matches!(
+            unused_id("s", |_| Ok(true)),
+            Err(SnapshotError::Exists(id)) if id == "s"
+        ));
+        assert!(matches!(
+            unused_id("s", |_| Err("denied".to_string())),
+            Err(SnapshotError::Write(_))
+        ));
+    }
+}
@@ -0,0 +1,161 @@
+//! The registry of key-value store actors and `/api/stores/:name/...` scoping.
+
+use crate::bindings::exports::ntwk::theater::http_server::HttpResponse;
+use crate::config::Config;
+use crate::listing::Entry;
+use crate::router::RequestContext;
+use crate::{diff, protocol, response, restore, State};
+use serde_json::{json, Value};
+
+/// First segments after `/api/` of the routes that act on one store, and so
+/// are also served as `/api/stores/:name/...`.
+const STORE_SCOPED: &[&str] = &[
+    "settings",
+    "store-contents",
+    "search",
+    "query",
+    "export",
+    "import",
+    "entries",
+    "snapshots",
+    "restore",
+];
+
+/// Strips a `/api/stores/:name` prefix from a store-scoped `path`, so the
+/// rest routes like the unprefixed API, and returns the actor id of the store
+/// it names. Other paths resolve to the default store and are left as they
+/// are.
+pub fn resolve(path: &mut Vec<String>, config: &Config) -> Result<String, HttpResponse> {
+    let scoped = path.len() >= 4
+        && path[0] == "api"
+        && path[1] == "stores"
+        && STORE_SCOPED.contains(&path[3].as_str());
+    let name = if scoped {
+        let name = path.remove(2);
+        path.remove(1);
+        name
+    } else {
+        config.default_store_name()
+    };
+    config.store(&name).ok_or_else(|| {
+        response::error_with_code(404, "unknown_store", &format!("No store named '{}'", name))
+    })
+}
+
+fn describe(name: &str, store_id: &str, is_default: bool, probe: bool) -> Value {
+    let mut store = json!({
+        "name": name,
+        "store_id": store_id,
+        "default": is_default,
+    });
+    if probe {
+        let result = protocol::ping(store_id);
+        store["reachable"] = json!(result.is_ok());
+        store["error"] = json!(result.err().map(|e| e.to_string()));
+    }
+    store
+}
+
+/// `GET /api/stores?probe=false|true`: the configured stores, each checked
+/// for reachability if `probe=true`.
+pub fn list(req: &RequestContext, state: &mut State) -> HttpResponse {
+    let probe = match req.query.parse::<bool>("probe") {
+        Ok(probe) => probe.unwrap_or(false),
+        Err(e) => return e.into_response(),
+    };
+    let default = state.config.default_store_name();
+    let stores: Vec<Value> = state
+        .config
+        .registry()
+        .iter()
+        .map(|(name, store_id)| describe(name, store_id, *name == default, probe))
+        .collect();
+    response::success(json!({ "default": default, "stores": stores }))
+}
+
+/// Loads one side of a diff: `file:<path>` reads a JSON dump,
+/// `snapshot:<id>` a snapshot, anything else names a configured store.
+fn load_side(spec: &str, state: &State) -> Result<Vec<Entry>, HttpResponse> {
+    if spec.starts_with("file:") || spec.starts_with("snapshot:") {
+        return restore::load_source(state, spec).map_err(|e| e.into_response());
+    }
+    let store_id = state.config.store(spec).ok_or_else(|| {
+        response::error_with_code(404, "unknown_store", &format!("No store named '{}'", spec))
+    })?;
+    state
+        .get_all_entries(&store_id)
+        .map_err(|e| e.into_response())
+}
+
+/// `GET /api/diff?left=A&right=B&include_identical=false`
+pub fn diff(req: &RequestContext, state: &mut State) -> HttpResponse {
+    let (Some(left), Some(right)) = (req.query.get("left"), req.query.get("right")) else {
+        return response::error_with_code(
+            400,
+            "invalid_parameter",
+            "Both 'left' and 'right' are required",
+        );
+    };
+    let include_identical = match req.query.parse::<bool>("include_identical") {
+        Ok(value) => value.unwrap_or(false),
+        Err(e) => return e.into_response(),
+    };
+
+    let (left_entries, right_entries) = match (load_side(left, state), load_side(right, state)) {
+        (Ok(l), Ok(r)) => (l, r),
+        (Err(e), _) | (_, Err(e)) => return e,
+    };
+    let diffs = diff::compare(left_entries, right_entries);
+    let summary = diff::summary(&diffs);
+    let keys: Vec<_> = diffs
+        .into_iter()
+        .filter(|d| include_identical || d.status != diff::Status::Identical)
+        .collect();
+
+    response::success(json!({
+        "left": left,
+        "right": right,
+        "summary": summary,
+        "keys": keys,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::path;
+
+    fn config() -> Config {
+        crate::test_support::config(r#"{"store_id": "kv", "stores": {"b": "kv-b"}}"#)
+    }
+
+    #[test]
+    fn strips_prefix_of_store_scoped_routes() {
+        let mut scoped = path(&["api", "stores", "b", "entries", "k"]);
+        assert_eq!(resolve(&mut scoped, &config()).unwrap(), "kv-b");
+        assert_eq!(scoped, path(&["api", "entries", "k"]));
+
+        let mut plain = path(&["api", "search"]);
+        assert_eq!(resolve(&mut plain, &config()).unwrap(), "kv");
+        assert_eq!(plain, path(&["api", "search"]));
+    }
+
+    #[test]
+    fn leaves_other_routes_alone() {
+        for other in [
+            path(&["api", "stores", "b", "admin", "config"]),
+            path(&["api", "stores", "b", "chain"]),
+            path(&["api", "stores", "b"]),
+        ] {
+            let mut resolved = other.clone();
+            assert_eq!(resolve(&mut resolved, &config()).unwrap(), "kv");
+            assert_eq!(resolved, other);
+        }
+    }
+
+    #[test]
+    fn unknown_store_is_not_found() {
+        let mut unknown = path(&["api", "stores", "nope", "entries"]);
+        assert_eq!(resolve(&mut unknown, &config()).unwrap_err().status, 404);
+    }
+}
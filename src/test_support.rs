@@ -0,0 +1,25 @@
+//! Helpers shared by the unit tests.
+
+use crate::config::Config;
+use crate::listing::Entry;
+
+/// Decoded path segments, as `router::split_uri` returns them.
+pub fn path(segments: &[&str]) -> Vec<String> {
+    segments.iter().map(|s| s.to_string()).collect()
+}
+
+pub fn entry(key: &str, value: &str) -> Entry {
+    Entry {
+        key: key.to_string(),
+        value: value.as_bytes().to_vec(),
+    }
+}
+
+pub fn entries(pairs: &[(&str, &str)]) -> Vec<Entry> {
+    pairs.iter().map(|(key, value)| entry(key, value)).collect()
+}
+
+/// A configuration parsed from init data, which must be valid.
+pub fn config(json: &str) -> Config {
+    Config::from_json(json.as_bytes()).unwrap()
+}
@@ -0,0 +1,78 @@
+//! Wall-clock time. The actor has no clock of its own, so requests carry the
+//! caller's time in `X-Client-Time` (milliseconds since the Unix epoch).
+
+use crate::router::RequestContext;
+
+pub const CLIENT_TIME_HEADER: &str = "X-Client-Time";
+
+/// The caller's time, if it sent one.
+pub fn client_time(req: &RequestContext) -> Option<u64> {
+    req.header(CLIENT_TIME_HEADER)?.trim().parse().ok()
+}
+
+/// Converts days since 1970-01-01 to a (year, month, day) civil date.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+fn parts(ms: u64) -> (i64, u32, u32, u64, u64, u64, u64) {
+    let secs = ms / 1000;
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    let rem = secs % 86_400;
+    (
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60,
+        ms % 1000,
+    )
+}
+
+/// `2024-05-01T12:30:00.000Z`
+pub fn to_rfc3339(ms: u64) -> String {
+    let (y, mo, d, h, mi, s, millis) = parts(ms);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        y, mo, d, h, mi, s, millis
+    )
+}
+
+/// `20240501T123000Z`, for file names.
+pub fn to_compact(ms: u64) -> String {
+    let (y, mo, d, h, mi, s, _) = parts(ms);
+    format!("{:04}{:02}{:02}T{:02}{:02}{:02}Z", y, mo, d, h, mi, s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn civil_dates_around_leap_days() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+        // 2100 is not a leap year.
+        assert_eq!(civil_from_days(47_540), (2100, 2, 28));
+        assert_eq!(civil_from_days(47_541), (2100, 3, 1));
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(to_rfc3339(0), "1970-01-01T00:00:00.000Z");
+        assert_eq!(to_rfc3339(1_714_566_600_123), "2024-05-01T12:30:00.123Z");
+        assert_eq!(to_rfc3339(946_684_799_999), "1999-12-31T23:59:59.999Z");
+        assert_eq!(to_compact(1_714_566_600_123), "20240501T123000Z");
+    }
+}
@@ -0,0 +1,157 @@
+//! Live updates for the UI over `websocket-server`.
+//!
+//! The interface only lets the actor answer incoming messages, so browsers
+//! subscribe once and then poll over the open socket; each poll is answered
+//! with the `entry_added` / `entry_changed` / `entry_removed` deltas since
+//! the sequence number the browser last saw (see `live.rs`). Stores that
+//! send change notifications (see `messages.rs`) are listed only once; for
+//! the others every poll compares a fresh listing with the previous one.
+//!
+//! Client messages: `subscribe`, `poll`, `unsubscribe` and `ping`, each a
+//! JSON text frame with a `type` and optionally `store`, `since` and `token`.
+
+use crate::bindings::exports::ntwk::theater::websocket_server::{MessageType, WebsocketMessage};
+use crate::bindings::ntwk::theater::runtime::log;
+use crate::State;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    Subscribe {
+        store: Option<String>,
+        token: Option<String>,
+    },
+    Poll {
+        store: Option<String>,
+        since: u64,
+        token: Option<String>,
+    },
+    Unsubscribe,
+    Ping,
+}
+
+fn text(value: Value) -> WebsocketMessage {
+    WebsocketMessage {
+        ty: MessageType::Text,
+        data: None,
+        text: Some(value.to_string()),
+    }
+}
+
+fn error(code: &str, message: &str) -> Vec<WebsocketMessage> {
+    vec![text(
+        json!({ "type": "error", "code": code, "message": message }),
+    )]
+}
+
+/// Resolves the store a client names (the default store if none) after
+/// checking its token. Returns the store's name and actor id.
+fn authorize(
+    state: &State,
+    store: Option<String>,
+    token: Option<&str>,
+) -> Result<(String, String), Vec<WebsocketMessage>> {
+    if !state.config.auth.accepts(token) {
+        return Err(error("unauthorized", "Missing or invalid token"));
+    }
+    let name = store.unwrap_or_else(|| state.config.default_store_name());
+    match state.config.store(&name) {
+        Some(store_id) => Ok((name, store_id)),
+        None => Err(error(
+            "unknown_store",
+            &format!("No store named '{}'", name),
+        )),
+    }
+}
+
+/// Reloads the store and records what changed since the last look.
+fn refresh(state: &mut State, store_id: &str) -> Result<(), Vec<WebsocketMessage>> {
+    let entries = state
+        .reload_entries(store_id)
+        .map_err(|e| error(e.code(), &e.to_string()))?;
+    state.changes.observe(store_id, &entries, "poll");
+    Ok(())
+}
+
+fn handle_client_message(state: &mut State, message: ClientMessage) -> Vec<WebsocketMessage> {
+    match message {
+        ClientMessage::Subscribe { store, token } => {
+            let (name, store_id) = match authorize(state, store, token.as_deref()) {
+                Ok(store) => store,
+                Err(reply) => return reply,
+            };
+            if !state.changes.is_tracked(&store_id) {
+                if let Err(reply) = refresh(state, &store_id) {
+                    return reply;
+                }
+            }
+            log(&format!("Websocket subscribed to {}", name));
+            vec![text(json!({
+                "type": "subscribed",
+                "store": name,
+                "version": state.changes.version(),
+            }))]
+        }
+        ClientMessage::Poll {
+            store,
+            since,
+            token,
+        } => {
+            let (name, store_id) = match authorize(state, store, token.as_deref()) {
+                Ok(store) => store,
+                Err(reply) => return reply,
+            };
+            if state.changes.needs_rescan(&store_id) {
+                if let Err(reply) = refresh(state, &store_id) {
+                    return reply;
+                }
+            }
+            let version = state.changes.version();
+            let Some(changes) = state.changes.since(&store_id, since) else {
+                return vec![text(json!({
+                    "type": "resync",
+                    "store": name,
+                    "version": version,
+                }))];
+            };
+            let mut replies: Vec<WebsocketMessage> = changes
+                .into_iter()
+                .map(|change| text(change.to_json(&name)))
+                .collect();
+            replies.push(text(json!({
+                "type": "synced",
+                "store": name,
+                "version": version,
+            })));
+            replies
+        }
+        ClientMessage::Unsubscribe => vec![text(json!({ "type": "unsubscribed" }))],
+        ClientMessage::Ping => vec![text(json!({ "type": "pong" }))],
+    }
+}
+
+/// Answers one websocket event with the messages to send back.
+pub fn handle_message(state: &mut State, message: WebsocketMessage) -> Vec<WebsocketMessage> {
+    match message.ty {
+        MessageType::Connect => vec![text(json!({
+            "type": "hello",
+            "version": state.changes.version(),
+        }))],
+        MessageType::Ping => vec![WebsocketMessage {
+            ty: MessageType::Pong,
+            data: message.data,
+            text: None,
+        }],
+        MessageType::Text => {
+            let body = message.text.unwrap_or_default();
+            match serde_json::from_str::<ClientMessage>(&body) {
+                Ok(client_message) => handle_client_message(state, client_message),
+                Err(e) => error("bad_request", &format!("Invalid message: {}", e)),
+            }
+        }
+        MessageType::Binary => error("bad_request", "Binary messages are not supported"),
+        MessageType::Close | MessageType::Pong | MessageType::Other(_) => Vec::new(),
+    }
+}